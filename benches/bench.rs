@@ -1,12 +1,14 @@
 mod setup;
 
 use self::setup::{
-    generate_test_strings, BackendBenchmark, BenchString, BENCH_LEN_STRINGS, BENCH_STRING_LEN,
+    generate_test_strings, BackendBenchmark, BenchPrefixed, BenchString, FxBuildHasher,
+    BENCH_LEN_STRINGS, BENCH_STRING_LEN,
 };
 use criterion::{
     black_box, criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup,
     Criterion, Throughput,
 };
+use string_hash_interner::{DefaultSymbol, InternerBuilder};
 
 criterion_group!(
     bench_resolve,
@@ -14,11 +16,16 @@ criterion_group!(
     bench_resolve_unchecked_already_filled
 );
 criterion_group!(bench_get, bench_get_already_filled);
-criterion_group!(bench_iter, bench_iter_already_filled);
+criterion_group!(
+    bench_iter,
+    bench_iter_already_filled,
+    bench_hashes_already_filled
+);
 criterion_group!(
     bench_get_or_intern,
     bench_get_or_intern_fill,
     bench_get_or_intern_fill_with_capacity,
+    bench_get_or_intern_fill_builder,
     bench_get_or_intern_already_filled,
 );
 criterion_main!(bench_get_or_intern, bench_resolve, bench_get, bench_iter);
@@ -45,6 +52,39 @@ fn bench_get_or_intern_fill_with_capacity(c: &mut Criterion) {
         );
     }
     bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchPrefixed>(&mut g);
+}
+
+fn bench_get_or_intern_fill_builder(c: &mut Criterion) {
+    let mut g = c.benchmark_group("intern/fill-empty/builder");
+    g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
+    g.bench_with_input(
+        "intern",
+        &(BENCH_LEN_STRINGS, BENCH_STRING_LEN),
+        |bencher, &(len_words, word_len)| {
+            let words = generate_test_strings(len_words, word_len);
+            bencher.iter(|| {
+                let mut interner = BenchString::setup();
+                let symbols = words
+                    .iter()
+                    .map(|word| interner.intern(word))
+                    .collect::<Vec<_>>();
+                black_box((interner, symbols))
+            })
+        },
+    );
+    g.bench_with_input(
+        "build_deduplicated",
+        &(BENCH_LEN_STRINGS, BENCH_STRING_LEN),
+        |bencher, &(len_words, word_len)| {
+            let words = generate_test_strings(len_words, word_len);
+            bencher.iter(|| {
+                let mut builder = InternerBuilder::<str, DefaultSymbol, FxBuildHasher>::new();
+                builder.extend(&words);
+                black_box(builder.build_deduplicated())
+            })
+        },
+    );
 }
 
 fn bench_get_or_intern_fill(c: &mut Criterion) {
@@ -69,6 +109,7 @@ fn bench_get_or_intern_fill(c: &mut Criterion) {
         );
     }
     bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchPrefixed>(&mut g);
 }
 
 fn bench_get_or_intern_already_filled(c: &mut Criterion) {
@@ -93,6 +134,7 @@ fn bench_get_or_intern_already_filled(c: &mut Criterion) {
         );
     }
     bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchPrefixed>(&mut g);
 }
 
 fn bench_resolve_already_filled(c: &mut Criterion) {
@@ -117,6 +159,7 @@ fn bench_resolve_already_filled(c: &mut Criterion) {
         );
     }
     bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchPrefixed>(&mut g);
 }
 
 fn bench_resolve_unchecked_already_filled(c: &mut Criterion) {
@@ -143,7 +186,28 @@ fn bench_resolve_unchecked_already_filled(c: &mut Criterion) {
             },
         );
     }
+    fn bench_index(g: &mut BenchmarkGroup<WallTime>) {
+        g.bench_with_input(
+            format!("{}/index", BenchString::NAME),
+            &(BENCH_LEN_STRINGS, BENCH_STRING_LEN),
+            |bencher, &(len_words, word_len)| {
+                let words = generate_test_strings(len_words, word_len);
+                let (interner, word_ids) = BenchString::setup_filled_with_ids(&words);
+                let index = interner.build_resolve_index().unwrap();
+                bencher.iter(|| {
+                    for &word_id in &word_ids {
+                        black_box(
+                            // SAFETY: We provide only valid symbols to the tested index.
+                            unsafe { index.resolve_unchecked(word_id) },
+                        );
+                    }
+                })
+            },
+        );
+    }
     bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchPrefixed>(&mut g);
+    bench_index(&mut g);
 }
 
 fn bench_get_already_filled(c: &mut Criterion) {
@@ -168,6 +232,7 @@ fn bench_get_already_filled(c: &mut Criterion) {
         );
     }
     bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchPrefixed>(&mut g);
 }
 
 fn bench_iter_already_filled(c: &mut Criterion) {
@@ -192,4 +257,30 @@ fn bench_iter_already_filled(c: &mut Criterion) {
         );
     }
     bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchPrefixed>(&mut g);
+}
+
+fn bench_hashes_already_filled(c: &mut Criterion) {
+    let mut g = c.benchmark_group("hashes/already-filled");
+    g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
+    fn bench_for_backend<BB: BackendBenchmark>(g: &mut BenchmarkGroup<WallTime>) {
+        g.bench_with_input(
+            BB::NAME,
+            &(BENCH_LEN_STRINGS, BENCH_STRING_LEN),
+            |bencher, &(len_words, word_len)| {
+                let words = generate_test_strings(len_words, word_len);
+                bencher.iter_batched_ref(
+                    || BB::setup_filled(&words),
+                    |interner| {
+                        for hash in interner.hashes() {
+                            black_box(hash);
+                        }
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    bench_for_backend::<BenchString>(&mut g);
+    bench_for_backend::<BenchPrefixed>(&mut g);
 }
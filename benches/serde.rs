@@ -0,0 +1,70 @@
+#[allow(dead_code)]
+mod setup;
+
+use self::setup::{generate_test_strings, FxBuildHasher, BENCH_LEN_STRINGS, BENCH_STRING_LEN};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use string_hash_interner::{DefaultSymbol, StringInterner};
+
+type Interner = StringInterner<DefaultSymbol, FxBuildHasher>;
+
+criterion_group!(
+    bench_deserialize,
+    bench_deserialize_json,
+    bench_deserialize_cbor
+);
+criterion_main!(bench_deserialize);
+
+fn setup_interner() -> Interner {
+    generate_test_strings(BENCH_LEN_STRINGS, BENCH_STRING_LEN)
+        .iter()
+        .collect()
+}
+
+/// Wrapper using the compact serialization format.
+struct Compact(Interner);
+
+impl serde::Serialize for Compact {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        string_hash_interner::serde_compact::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Compact {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        string_hash_interner::serde_compact::deserialize(deserializer).map(Compact)
+    }
+}
+
+fn bench_deserialize_json(c: &mut Criterion) {
+    let mut g = c.benchmark_group("deserialize/json");
+    g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
+    let interner = setup_interner();
+
+    let json = serde_json::to_vec(&interner).unwrap();
+    g.bench_function("sequence", |bencher| {
+        bencher.iter(|| black_box(serde_json::from_slice::<Interner>(&json).unwrap()))
+    });
+
+    let json = serde_json::to_vec(&Compact(interner)).unwrap();
+    g.bench_function("compact", |bencher| {
+        bencher.iter(|| black_box(serde_json::from_slice::<Compact>(&json).unwrap()))
+    });
+}
+
+fn bench_deserialize_cbor(c: &mut Criterion) {
+    let mut g = c.benchmark_group("deserialize/cbor");
+    g.throughput(Throughput::Elements(BENCH_LEN_STRINGS as u64));
+    let interner = setup_interner();
+
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&interner, &mut cbor).unwrap();
+    g.bench_function("sequence", |bencher| {
+        bencher.iter(|| black_box(ciborium::from_reader::<Interner, _>(&cbor[..]).unwrap()))
+    });
+
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&Compact(interner), &mut cbor).unwrap();
+    g.bench_function("compact", |bencher| {
+        bencher.iter(|| black_box(ciborium::from_reader::<Compact, _>(&cbor[..]).unwrap()))
+    });
+}
@@ -1,4 +1,7 @@
-use string_hash_interner::{DefaultSymbol, StringInterner};
+use string_hash_interner::{
+    backend::{HashedBackend, PrefixedBackend, StringBackend},
+    DefaultSymbol, StringInterner,
+};
 
 /// Alphabet containing all characters that may be put into a benchmark string.
 const ALPHABET: [u8; 64] = [
@@ -74,25 +77,28 @@ pub const BENCH_LEN_STRINGS: usize = 100_000;
 /// The length of a single interned string.
 pub const BENCH_STRING_LEN: usize = 5;
 
-type FxBuildHasher = fxhash::FxBuildHasher;
-type StringInternerWith = StringInterner<DefaultSymbol, FxBuildHasher>;
+pub type FxBuildHasher = fxhash::FxBuildHasher;
+type StringInternerWith<B> = StringInterner<DefaultSymbol, FxBuildHasher, B>;
 
 pub trait BackendBenchmark {
     const NAME: &'static str;
+    type Backend: HashedBackend<str, DefaultSymbol>;
 
-    fn setup() -> StringInternerWith {
+    fn setup() -> StringInternerWith<Self::Backend> {
         StringInternerWith::new()
     }
 
-    fn setup_with_capacity(cap: usize) -> StringInternerWith {
+    fn setup_with_capacity(cap: usize) -> StringInternerWith<Self::Backend> {
         StringInternerWith::with_capacity(cap)
     }
 
-    fn setup_filled(words: &[String]) -> StringInternerWith {
-        words.iter().collect::<StringInternerWith>()
+    fn setup_filled(words: &[String]) -> StringInternerWith<Self::Backend> {
+        words.iter().collect::<StringInternerWith<Self::Backend>>()
     }
 
-    fn setup_filled_with_ids(words: &[String]) -> (StringInternerWith, Vec<DefaultSymbol>) {
+    fn setup_filled_with_ids(
+        words: &[String],
+    ) -> (StringInternerWith<Self::Backend>, Vec<DefaultSymbol>) {
         let mut interner = StringInternerWith::new();
         let word_ids = words
             .iter()
@@ -105,4 +111,11 @@ pub trait BackendBenchmark {
 pub struct BenchString;
 impl BackendBenchmark for BenchString {
     const NAME: &'static str = "StringBackend";
+    type Backend = StringBackend<str, DefaultSymbol>;
+}
+
+pub struct BenchPrefixed;
+impl BackendBenchmark for BenchPrefixed {
+    const NAME: &'static str = "PrefixedBackend";
+    type Backend = PrefixedBackend<str, DefaultSymbol>;
 }
@@ -0,0 +1,449 @@
+use super::{Backend, HashedBackend};
+use crate::{intern::Intern, symbol::expect_valid_symbol, InternError, MemoryUsage, Symbol};
+use alloc::{collections::TryReserveError, vec::Vec};
+use core::{
+    fmt::Debug,
+    iter::{Enumerate, FusedIterator},
+    marker::PhantomData,
+    mem, slice,
+};
+
+/// An interner backend that stores strings in buckets which are never reallocated.
+///
+/// A string is always stored as a whole in the last bucket. If it doesn't fit
+/// into the remaining capacity of that bucket, a new bucket is allocated instead of
+/// growing the old one. So the contents of the interned strings never move in memory,
+/// and pointers to the strings returned by [`Interner::resolve`](crate::Interner::resolve)
+/// stay valid across further interning, for as long as the strings are not removed.
+/// [`Interner::retain`](crate::Interner::retain) is the exception: it copies the kept
+/// strings into new buckets, so they move.
+///
+/// # Note
+///
+/// The borrow checker still ties references returned by
+/// [`Interner::resolve`](crate::Interner::resolve) to the borrow of the interner.
+/// The stable addresses are useful for data structures that keep raw pointers
+/// to the interned strings.
+///
+/// Unlike the [`StringBackend`](super::StringBackend), the buckets are not shrunk
+/// by [`Backend::shrink_to_fit`], since that would move the strings.
+///
+/// Strings interned with [`Interner::intern_static`](crate::Interner::intern_static)
+/// are not copied into the buckets, only a reference to them is stored.
+/// That costs 16 bytes per string on 64-bit targets instead of its contents,
+/// and one more branch when resolving any string.
+pub struct BucketBackend<I: Intern + ?Sized, S> {
+    /// Stores the bucket, the span in it, and the hash of every string.
+    spans: Vec<BucketSpan>,
+    buckets: Vec<Vec<I::Primitive>>,
+    /// Stores the `'static` strings, which are referenced instead of copied.
+    statics: Vec<&'static [I::Primitive]>,
+    marker: PhantomData<fn(&I) -> S>,
+}
+
+/// The span of a string.
+///
+/// If `bucket` has the [`STATIC_BUCKET`] bit set, the rest of it is an index
+/// into the `'static` strings instead.
+#[derive(Debug, Clone, Copy)]
+struct BucketSpan {
+    bucket: usize,
+    from: usize,
+    to: usize,
+    hash: u64,
+}
+
+/// The minimum number of primitives a new bucket can hold.
+const MIN_BUCKET_LEN: usize = 4096;
+
+/// Tags the spans of `'static` strings.
+const STATIC_BUCKET: usize = 1 << (usize::BITS - 1);
+
+impl<I: Intern + ?Sized, S> Debug for BucketBackend<I, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BucketBackend")
+            .field("spans", &self.spans)
+            .field("buckets", &self.buckets)
+            .field("statics", &self.statics)
+            .finish()
+    }
+}
+
+impl<I: Intern + ?Sized, S> Clone for BucketBackend<I, S> {
+    fn clone(&self) -> Self {
+        Self {
+            spans: self.spans.clone(),
+            buckets: self.buckets.clone(),
+            statics: self.statics.clone(),
+            marker: PhantomData,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.spans.clone_from(&source.spans);
+        self.buckets.clone_from(&source.buckets);
+        self.statics.clone_from(&source.statics);
+    }
+}
+
+impl<I: Intern + ?Sized, S> Default for BucketBackend<I, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self {
+            spans: Vec::default(),
+            buckets: Vec::default(),
+            statics: Vec::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<I: Intern + ?Sized, S> BucketBackend<I, S> {
+    /// Returns `true` if the last bucket has room for `additional` more primitives.
+    fn has_room(&self, additional: usize) -> bool {
+        self.buckets
+            .last()
+            .is_some_and(|bucket| bucket.capacity() - bucket.len() >= additional)
+    }
+
+    /// Returns the last bucket, with room for at least `additional` more primitives
+    /// without reallocating.
+    fn bucket_with_room(&mut self, additional: usize) -> &mut Vec<I::Primitive> {
+        if !self.has_room(additional) {
+            self.buckets
+                .push(Vec::with_capacity(additional.max(MIN_BUCKET_LEN)));
+        }
+        self.buckets.last_mut().unwrap()
+    }
+
+    /// Returns the string of the given span.
+    fn span_to_str(&self, span: &BucketSpan) -> &I {
+        span_to_str(&self.buckets, &self.statics, span)
+    }
+}
+
+/// Returns the string of the given span.
+#[inline]
+fn span_to_str<'a, I: Intern + ?Sized>(
+    buckets: &'a [Vec<I::Primitive>],
+    statics: &[&'static [I::Primitive]],
+    span: &BucketSpan,
+) -> &'a I {
+    let bytes = if span.bucket & STATIC_BUCKET == 0 {
+        &buckets[span.bucket][span.from..span.to]
+    } else {
+        statics[span.bucket & !STATIC_BUCKET]
+    };
+    // SAFETY: This span is guaranteed to be valid
+    unsafe { I::from_bytes(bytes) }
+}
+
+impl<I: Intern + ?Sized, S: Symbol> Backend<I, S> for BucketBackend<I, S> {
+    type Iter<'a>
+        = BucketIter<'a, I, S>
+    where
+        Self: 'a,
+        I: 'a;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity(cap: usize) -> Self {
+        // According to google the approx. word length is 5. So we will use 10.
+        const DEFAULT_WORD_LEN: usize = 10;
+        Self::with_capacities(cap, cap * DEFAULT_WORD_LEN)
+    }
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacities(cap: usize, buffer_cap: usize) -> Self {
+        let mut backend = Self {
+            spans: Vec::with_capacity(cap),
+            buckets: Vec::new(),
+            statics: Vec::new(),
+            marker: PhantomData,
+        };
+        if buffer_cap > 0 {
+            backend.bucket_with_room(buffer_cap);
+        }
+        backend
+    }
+
+    #[inline]
+    fn try_intern(&mut self, string: &I, hash: u64) -> Result<S, InternError> {
+        let symbol = S::try_from_usize(self.spans.len()).ok_or(InternError::SymbolOverflow)?;
+        let bytes = string.as_bytes();
+        let bucket = self.bucket_with_room(bytes.len());
+        let from = bucket.len();
+        bucket.extend_from_slice(bytes);
+        let to = bucket.len();
+        self.spans.push(BucketSpan {
+            bucket: self.buckets.len() - 1,
+            from,
+            to,
+            hash,
+        });
+        Ok(symbol)
+    }
+
+    #[inline]
+    fn try_intern_static(&mut self, string: &'static I, hash: u64) -> Result<S, InternError> {
+        let symbol = S::try_from_usize(self.spans.len()).ok_or(InternError::SymbolOverflow)?;
+        let bytes = string.as_bytes();
+        self.spans.push(BucketSpan {
+            bucket: STATIC_BUCKET | self.statics.len(),
+            from: 0,
+            to: bytes.len(),
+            hash,
+        });
+        self.statics.push(bytes);
+        Ok(symbol)
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&I> {
+        let span = self.spans.get(symbol.to_usize())?;
+        Some(self.span_to_str(span))
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &I {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let span = unsafe { self.spans.get_unchecked(symbol.to_usize()) };
+        self.span_to_str(span)
+    }
+
+    #[inline]
+    fn byte_len(&self, symbol: S) -> Option<usize> {
+        let span = self.spans.get(symbol.to_usize())?;
+        Some(span.to - span.from)
+    }
+
+    #[inline]
+    unsafe fn byte_len_unchecked(&self, symbol: S) -> usize {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let span = unsafe { self.spans.get_unchecked(symbol.to_usize()) };
+        span.to - span.from
+    }
+
+    #[inline]
+    unsafe fn hash_unchecked(&self, symbol: S, _make_hash: impl FnOnce(&I) -> u64) -> u64 {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { self.get_hash_unchecked(symbol) }
+    }
+
+    fn rehash(&mut self, mut make_hash: impl FnMut(&I) -> u64) {
+        for span in &mut self.spans {
+            span.hash = make_hash(span_to_str(&self.buckets, &self.statics, span));
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.spans.capacity()
+    }
+
+    fn buffer_capacity(&self) -> usize {
+        self.buckets.iter().map(Vec::capacity).sum()
+    }
+
+    fn buffer_len(&self) -> usize {
+        self.buckets.iter().map(Vec::len).sum()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.spans.reserve(additional);
+    }
+
+    fn reserve_buffer(&mut self, additional: usize) {
+        self.bucket_with_room(additional);
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.spans.try_reserve(additional)
+    }
+
+    fn try_reserve_buffer(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        if !self.has_room(additional) {
+            let mut bucket = Vec::new();
+            bucket.try_reserve_exact(additional.max(MIN_BUCKET_LEN))?;
+            self.buckets.try_reserve(1)?;
+            self.buckets.push(bucket);
+        }
+        Ok(())
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.spans.shrink_to_fit();
+        self.buckets.shrink_to_fit();
+        self.statics.shrink_to_fit();
+    }
+
+    fn shrink_to(&mut self, min_cap: usize, min_buffer_cap: usize) {
+        // The buckets themselves are never shrunk, so `min_buffer_cap` is always kept.
+        let _ = min_buffer_cap;
+        self.spans.shrink_to(min_cap);
+        self.buckets.shrink_to_fit();
+        self.statics.shrink_to_fit();
+    }
+
+    fn clear(&mut self) {
+        self.spans.clear();
+        self.statics.clear();
+        // Keep the last bucket, which is the most recently allocated one.
+        let last = self.buckets.pop();
+        self.buckets.clear();
+        if let Some(mut last) = last {
+            last.clear();
+            self.buckets.push(last);
+        }
+    }
+
+    fn truncate(&mut self, len: usize) {
+        let Some(removed) = self.spans.get(len..) else {
+            return;
+        };
+        // The removed strings are the last ones in their buckets and the `'static` strings,
+        // so the first removed one of each kind marks where to cut.
+        if let Some(span) = removed.iter().find(|span| span.bucket & STATIC_BUCKET == 0) {
+            self.buckets.truncate(span.bucket + 1);
+            self.buckets[span.bucket].truncate(span.from);
+        }
+        if let Some(span) = removed.iter().find(|span| span.bucket & STATIC_BUCKET != 0) {
+            self.statics.truncate(span.bucket & !STATIC_BUCKET);
+        }
+        self.spans.truncate(len);
+    }
+
+    /// Copies the kept strings of the buckets into new buckets, so they are moved
+    /// in memory. The kept `'static` strings are still stored by reference.
+    fn retain(&mut self, mut f: impl FnMut(S, &I) -> bool, _make_hash: impl FnMut(&I) -> u64) {
+        let mut backend = Self::default();
+        for (index, span) in self.spans.iter().enumerate() {
+            let string = self.span_to_str(span);
+            if !f(expect_valid_symbol(index), string) {
+                continue;
+            }
+            if span.bucket & STATIC_BUCKET == 0 {
+                backend
+                    .try_intern(string, span.hash)
+                    .expect("encountered invalid symbol");
+            } else {
+                backend.spans.push(BucketSpan {
+                    bucket: STATIC_BUCKET | backend.statics.len(),
+                    ..*span
+                });
+                backend
+                    .statics
+                    .push(self.statics[span.bucket & !STATIC_BUCKET]);
+            }
+        }
+        *self = backend;
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            buffer_bytes: self.buffer_capacity() * mem::size_of::<I::Primitive>(),
+            ends_bytes: self.spans.capacity() * mem::size_of::<BucketSpan>()
+                + self.buckets.capacity() * mem::size_of::<Vec<I::Primitive>>()
+                + self.statics.capacity() * mem::size_of::<&[I::Primitive]>(),
+            dedup_bytes: 0,
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> BucketIter<'_, I, S> {
+        BucketIter {
+            inner: self.iter_with_hashes(),
+        }
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol> HashedBackend<I, S> for BucketBackend<I, S> {
+    type IterWithHashes<'a>
+        = BucketIterWithHashes<'a, I, S>
+    where
+        Self: 'a,
+        I: 'a;
+
+    fn get_hash(&self, symbol: S) -> Option<u64> {
+        self.spans.get(symbol.to_usize()).map(|span| span.hash)
+    }
+
+    unsafe fn get_hash_unchecked(&self, symbol: S) -> u64 {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { self.spans.get_unchecked(symbol.to_usize()).hash }
+    }
+
+    #[inline]
+    fn iter_with_hashes(&self) -> BucketIterWithHashes<'_, I, S> {
+        BucketIterWithHashes {
+            buckets: &self.buckets,
+            statics: &self.statics,
+            spans: self.spans.iter().enumerate(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the symbols, strings, and hashes of a [`BucketBackend`].
+pub struct BucketIterWithHashes<'a, I: Intern + ?Sized, S> {
+    buckets: &'a [Vec<I::Primitive>],
+    statics: &'a [&'static [I::Primitive]],
+    spans: Enumerate<slice::Iter<'a, BucketSpan>>,
+    marker: PhantomData<fn() -> (S, &'a I)>,
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol> Iterator for BucketIterWithHashes<'a, I, S> {
+    type Item = (S, &'a I, u64);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (id, span) = self.spans.next()?;
+        let string = span_to_str(self.buckets, self.statics, span);
+        Some((expect_valid_symbol(id), string, span.hash))
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol> ExactSizeIterator for BucketIterWithHashes<'_, I, S> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.spans.len()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol> FusedIterator for BucketIterWithHashes<'_, I, S> {}
+
+/// An iterator over the symbols and strings of a [`BucketBackend`].
+pub struct BucketIter<'a, I: Intern + ?Sized, S> {
+    inner: BucketIterWithHashes<'a, I, S>,
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol> Iterator for BucketIter<'a, I, S> {
+    type Item = (S, &'a I);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (symbol, string, _hash) = self.inner.next()?;
+        Some((symbol, string))
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol> ExactSizeIterator for BucketIter<'_, I, S> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol> FusedIterator for BucketIter<'_, I, S> {}
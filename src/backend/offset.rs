@@ -0,0 +1,58 @@
+use core::fmt::Debug;
+
+/// Types implementing this trait can be used as offsets into the buffer of a
+/// [`StringBackend`](super::StringBackend).
+///
+/// # Note
+///
+/// The offset type limits the total length of all interned strings.
+/// For example, with `u32` offsets the buffer can hold at most `u32::MAX` elements.
+///
+/// # Safety
+///
+/// The offsets are trusted to split the buffer at the ends of strings, so
+/// [`Offset::to_usize`] must return the value the offset was created from:
+/// `Offset::try_from_usize(n).map(Offset::to_usize)` must be `None` or `Some(n)`.
+pub unsafe trait Offset: Copy + Eq + Debug {
+    /// Creates an offset from a `usize`.
+    ///
+    /// Returns `None` if `offset` is out of bounds for the offset type.
+    fn try_from_usize(offset: usize) -> Option<Self>;
+
+    /// Returns the `usize` representation of `self`.
+    fn to_usize(self) -> usize;
+}
+
+// SAFETY: The offset is the `usize` itself.
+unsafe impl Offset for usize {
+    #[inline]
+    fn try_from_usize(offset: usize) -> Option<Self> {
+        Some(offset)
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize {
+        self
+    }
+}
+
+macro_rules! impl_offset_for {
+    ( $($ty:ty),* ) => {
+        $(
+            // SAFETY: `try_from` fails for values that don't fit into the type,
+            //         so casting back gives the same value.
+            unsafe impl Offset for $ty {
+                #[inline]
+                fn try_from_usize(offset: usize) -> Option<Self> {
+                    <$ty>::try_from(offset).ok()
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+impl_offset_for!(u16, u32);
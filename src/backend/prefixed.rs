@@ -0,0 +1,404 @@
+use super::{Backend, HashedBackend};
+use crate::{intern::Intern, symbol::expect_valid_symbol, InternError, Symbol};
+use alloc::{collections::TryReserveError, vec::Vec};
+use core::{
+    fmt::Debug,
+    iter::{Enumerate, FusedIterator},
+    marker::PhantomData,
+    slice,
+};
+
+/// An interner backend that stores every string right after its length in one buffer.
+///
+/// The buffer has the layout `[len][string][len][string]...`, where every `len`
+/// is [LEB128](https://en.wikipedia.org/wiki/LEB128) encoded, so it takes a single byte
+/// for strings shorter than 128 bytes.
+///
+/// Iteration walks the buffer sequentially, reading each string right after its length.
+/// The start of every entry is still stored in a separate index together with the hash,
+/// so that resolution stays a constant time operation.
+///
+/// Only available for strings with `u8` as [`Intern::Primitive`].
+pub struct PrefixedBackend<I: Intern + ?Sized, S> {
+    /// Stores start of the entry and hash of it's string
+    starts: Vec<(usize, u64)>,
+    buffer: Vec<u8>,
+    marker: PhantomData<fn(&I) -> S>,
+}
+
+impl<I: Intern + ?Sized, S> Debug for PrefixedBackend<I, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PrefixedBackend")
+            .field("starts", &self.starts)
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+impl<I: Intern + ?Sized, S> Clone for PrefixedBackend<I, S> {
+    fn clone(&self) -> Self {
+        Self {
+            starts: self.starts.clone(),
+            buffer: self.buffer.clone(),
+            marker: PhantomData,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.starts.clone_from(&source.starts);
+        self.buffer.clone_from(&source.buffer);
+    }
+}
+
+impl<I: Intern + ?Sized, S> Default for PrefixedBackend<I, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self {
+            starts: Vec::default(),
+            buffer: Vec::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Appends the LEB128 encoded `len` to the `buffer`.
+#[inline]
+fn encode_len(buffer: &mut Vec<u8>, mut len: usize) {
+    while len >= 0x80 {
+        buffer.push(len as u8 | 0x80);
+        len >>= 7;
+    }
+    buffer.push(len as u8);
+}
+
+/// Returns the span of the string of the entry starting at `start`.
+#[inline]
+fn decode_span(buffer: &[u8], start: usize) -> (usize, usize) {
+    let first = buffer[start];
+    if first < 0x80 {
+        return (start + 1, start + 1 + first as usize);
+    }
+    decode_span_cold(buffer, start)
+}
+
+#[cold]
+fn decode_span_cold(buffer: &[u8], start: usize) -> (usize, usize) {
+    let mut len = 0;
+    let mut shift = 0;
+    let mut pos = start;
+    loop {
+        let byte = buffer[pos];
+        pos += 1;
+        len |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return (pos, pos + len);
+        }
+        shift += 7;
+    }
+}
+
+impl<I: Intern<Primitive = u8> + ?Sized, S: Symbol> PrefixedBackend<I, S> {
+    /// Returns the string of the entry starting at `start`.
+    ///
+    /// # Safety
+    ///
+    /// `start` must be a start of an entry within the [Self::buffer]
+    unsafe fn entry_to_str(&self, start: usize) -> &I {
+        let (from, to) = decode_span(&self.buffer, start);
+        unsafe { I::from_bytes(&self.buffer[from..to]) }
+    }
+}
+
+impl<I: Intern<Primitive = u8> + ?Sized, S: Symbol> Backend<I, S> for PrefixedBackend<I, S> {
+    type Iter<'a>
+        = PrefixedIter<'a, I, S>
+    where
+        Self: 'a,
+        I: 'a;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity(cap: usize) -> Self {
+        // According to google the approx. word length is 5. So we will use 10,
+        // plus a single byte for the length.
+        const DEFAULT_ENTRY_LEN: usize = 11;
+        Self {
+            starts: Vec::with_capacity(cap),
+            buffer: Vec::with_capacity(cap * DEFAULT_ENTRY_LEN),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn try_intern(&mut self, string: &I, hash: u64) -> Result<S, InternError> {
+        let symbol = S::try_from_usize(self.starts.len()).ok_or(InternError::SymbolOverflow)?;
+        let bytes = string.as_bytes();
+        let start = self.buffer.len();
+        encode_len(&mut self.buffer, bytes.len());
+        self.buffer.extend_from_slice(bytes);
+        self.starts.push((start, hash));
+        Ok(symbol)
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&I> {
+        let start = self.starts.get(symbol.to_usize())?.0;
+
+        // SAFETY: This entry is guaranteed to be valid
+        unsafe { Some(self.entry_to_str(start)) }
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &I {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let start = unsafe { self.starts.get_unchecked(symbol.to_usize()).0 };
+
+        // SAFETY: This entry is guaranteed to be valid
+        unsafe { self.entry_to_str(start) }
+    }
+
+    #[inline]
+    unsafe fn hash_unchecked(&self, symbol: S, _make_hash: impl FnOnce(&I) -> u64) -> u64 {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { self.get_hash_unchecked(symbol) }
+    }
+
+    fn rehash(&mut self, mut make_hash: impl FnMut(&I) -> u64) {
+        for (start, hash) in &mut self.starts {
+            let (from, to) = decode_span(&self.buffer, *start);
+            // SAFETY: This span is guaranteed to be valid
+            *hash = make_hash(unsafe { I::from_bytes(&self.buffer[from..to]) });
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.starts.capacity()
+    }
+
+    fn buffer_capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    fn buffer_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.starts.reserve(additional);
+    }
+
+    fn reserve_buffer(&mut self, additional: usize) {
+        self.buffer.reserve(additional);
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.starts.try_reserve(additional)
+    }
+
+    fn try_reserve_buffer(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buffer.try_reserve(additional)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.starts.shrink_to_fit();
+        self.buffer.shrink_to_fit();
+    }
+
+    fn shrink_to(&mut self, min_cap: usize, min_buffer_cap: usize) {
+        self.starts.shrink_to(min_cap);
+        self.buffer.shrink_to(min_buffer_cap);
+    }
+
+    fn clear(&mut self) {
+        self.starts.clear();
+        self.buffer.clear();
+    }
+
+    fn truncate(&mut self, len: usize) {
+        if let Some(&(start, _)) = self.starts.get(len) {
+            self.starts.truncate(len);
+            self.buffer.truncate(start);
+        }
+    }
+
+    fn retain(&mut self, mut f: impl FnMut(S, &I) -> bool, _make_hash: impl FnMut(&I) -> u64) {
+        // Decide about all strings first, so that a panic in `f` leaves the backend unchanged.
+        let keep: Vec<bool> = self
+            .iter()
+            .map(|(symbol, string)| f(symbol, string))
+            .collect();
+        let mut len = 0;
+        let mut end = 0;
+        for (index, keep) in keep.into_iter().enumerate() {
+            if keep {
+                let (start, hash) = self.starts[index];
+                let (_, to) = decode_span(&self.buffer, start);
+                self.buffer.copy_within(start..to, end);
+                self.starts[len] = (end, hash);
+                end += to - start;
+                len += 1;
+            }
+        }
+        self.starts.truncate(len);
+        self.buffer.truncate(end);
+    }
+
+    #[inline]
+    fn iter(&self) -> PrefixedIter<'_, I, S> {
+        PrefixedIter::new(self)
+    }
+}
+
+impl<I: Intern<Primitive = u8> + ?Sized, S: Symbol> HashedBackend<I, S> for PrefixedBackend<I, S> {
+    type IterWithHashes<'a>
+        = PrefixedIterWithHashes<'a, I, S>
+    where
+        Self: 'a,
+        I: 'a;
+
+    fn get_hash(&self, symbol: S) -> Option<u64> {
+        self.starts.get(symbol.to_usize()).map(|&(_, hash)| hash)
+    }
+
+    unsafe fn get_hash_unchecked(&self, symbol: S) -> u64 {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { self.starts.get_unchecked(symbol.to_usize()).1 }
+    }
+
+    #[inline]
+    fn resolve_with_hash(&self, symbol: S) -> Option<(&I, u64)> {
+        let &(start, hash) = self.starts.get(symbol.to_usize())?;
+        // SAFETY: This entry is guaranteed to be valid
+        Some((unsafe { self.entry_to_str(start) }, hash))
+    }
+
+    #[inline]
+    unsafe fn resolve_with_hash_unchecked(&self, symbol: S) -> (&I, u64) {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let &(start, hash) = unsafe { self.starts.get_unchecked(symbol.to_usize()) };
+
+        // SAFETY: This entry is guaranteed to be valid
+        (unsafe { self.entry_to_str(start) }, hash)
+    }
+
+    #[inline]
+    fn iter_with_hashes(&self) -> PrefixedIterWithHashes<'_, I, S> {
+        PrefixedIterWithHashes::new(self)
+    }
+}
+
+/// An iterator over the symbols and strings of a [`PrefixedBackend`].
+///
+/// Walks the buffer without touching the index of entries.
+pub struct PrefixedIter<'a, I: Intern + ?Sized, S> {
+    buffer: &'a [u8],
+    pos: usize,
+    index: usize,
+    len: usize,
+    marker: PhantomData<fn() -> (S, &'a I)>,
+}
+
+impl<'a, I: Intern + ?Sized, S> PrefixedIter<'a, I, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn new(backend: &'a PrefixedBackend<I, S>) -> Self {
+        Self {
+            buffer: &backend.buffer,
+            pos: 0,
+            index: 0,
+            len: backend.starts.len(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, I: Intern<Primitive = u8> + ?Sized, S: Symbol> Iterator for PrefixedIter<'a, I, S> {
+    type Item = (S, &'a I);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        let (from, to) = decode_span(self.buffer, self.pos);
+        self.pos = to;
+        let id = self.index;
+        self.index += 1;
+
+        // SAFETY: This span is guaranteed to be valid
+        let string = unsafe { I::from_bytes(&self.buffer[from..to]) };
+
+        Some((expect_valid_symbol(id), string))
+    }
+}
+
+impl<I: Intern<Primitive = u8> + ?Sized, S: Symbol> ExactSizeIterator for PrefixedIter<'_, I, S> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len - self.index
+    }
+}
+
+impl<I: Intern<Primitive = u8> + ?Sized, S: Symbol> FusedIterator for PrefixedIter<'_, I, S> {}
+
+/// An iterator over the symbols, strings, and hashes of a [`PrefixedBackend`].
+pub struct PrefixedIterWithHashes<'a, I: Intern + ?Sized, S> {
+    buffer: &'a [u8],
+    starts: Enumerate<slice::Iter<'a, (usize, u64)>>,
+    marker: PhantomData<fn() -> (S, &'a I)>,
+}
+
+impl<'a, I: Intern + ?Sized, S> PrefixedIterWithHashes<'a, I, S> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn new(backend: &'a PrefixedBackend<I, S>) -> Self {
+        Self {
+            buffer: &backend.buffer,
+            starts: backend.starts.iter().enumerate(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, I: Intern<Primitive = u8> + ?Sized, S: Symbol> Iterator
+    for PrefixedIterWithHashes<'a, I, S>
+{
+    type Item = (S, &'a I, u64);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.starts.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (id, &(start, hash)) = self.starts.next()?;
+        let (from, to) = decode_span(self.buffer, start);
+
+        // SAFETY: This span is guaranteed to be valid
+        let string = unsafe { I::from_bytes(&self.buffer[from..to]) };
+
+        Some((expect_valid_symbol(id), string, hash))
+    }
+}
+
+impl<I: Intern<Primitive = u8> + ?Sized, S: Symbol> ExactSizeIterator
+    for PrefixedIterWithHashes<'_, I, S>
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.starts.len()
+    }
+}
+
+impl<I: Intern<Primitive = u8> + ?Sized, S: Symbol> FusedIterator
+    for PrefixedIterWithHashes<'_, I, S>
+{
+}
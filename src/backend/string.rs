@@ -0,0 +1,756 @@
+use super::{Backend, HashedBackend, Offset};
+use crate::{
+    intern::Intern, symbol::expect_valid_symbol, InternError, MemoryUsage, ResolveTable, Symbol,
+};
+use alloc::{collections::TryReserveError, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem, slice, str,
+};
+
+/// An interner backend that accumulates all interned string contents into one string.
+///
+/// The ends of the strings in the buffer and the hashes of the strings are stored
+/// in two separate vectors, so that resolving a symbol doesn't touch the hashes,
+/// and walking over the hashes doesn't touch the ends.
+///
+/// Every end is stored as an [`Offset`] of type `O`, which limits the total length
+/// of the buffer. Interning a string that doesn't fit fails with [`InternError::OffsetOverflow`].
+/// With `u32` offsets, every interned string takes 12 bytes besides its contents,
+/// instead of 16 bytes with `usize` offsets on 64-bit targets.
+///
+/// If `CACHE_HASHES` is `false`, the hashes are not stored at all, saving 8 bytes
+/// for every interned string. The backend doesn't implement [`HashedBackend`] then,
+/// so methods like [`Interner::get_hash`](crate::Interner::get_hash) are not available,
+/// and the [`Interner`](crate::Interner) hashes the strings again when its deduplication map grows.
+///
+/// ```compile_fail
+/// use string_hash_interner::{backend::StringBackend, DefaultHashBuilder, DefaultSymbol, StringInterner};
+///
+/// type Backend = StringBackend<str, DefaultSymbol, usize, false>;
+/// let mut interner = StringInterner::<DefaultSymbol, DefaultHashBuilder, Backend>::new();
+/// let symbol = interner.intern("hello");
+/// interner.get_hash(symbol);
+/// ```
+///
+/// # Note
+///
+/// Implementation inspired by [CAD97's](https://github.com/CAD97) research
+/// project [`strena`](https://github.com/CAD97/strena).
+///
+pub struct StringBackend<I: Intern + ?Sized, S, O = usize, const CACHE_HASHES: bool = true> {
+    /// Stores end of every string
+    ends: Vec<O>,
+    /// Stores hash of every string, stays empty unless `CACHE_HASHES` is set
+    hashes: Vec<u64>,
+    buffer: Vec<I::Primitive>,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<I: Intern + ?Sized, S, O: Offset, const CACHE_HASHES: bool> Debug
+    for StringBackend<I, S, O, CACHE_HASHES>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StringBackend")
+            .field("ends", &self.ends)
+            .field("hashes", &self.hashes)
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+impl<I: Intern + ?Sized, S, O: Offset, const CACHE_HASHES: bool> Clone
+    for StringBackend<I, S, O, CACHE_HASHES>
+{
+    fn clone(&self) -> Self {
+        Self {
+            ends: self.ends.clone(),
+            hashes: self.hashes.clone(),
+            buffer: self.buffer.clone(),
+            marker: PhantomData,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.ends.clone_from(&source.ends);
+        self.hashes.clone_from(&source.hashes);
+        self.buffer.clone_from(&source.buffer);
+    }
+}
+
+impl<I: Intern + ?Sized, S, O, const CACHE_HASHES: bool> Default
+    for StringBackend<I, S, O, CACHE_HASHES>
+{
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Intern + ?Sized, S, O, const CACHE_HASHES: bool> StringBackend<I, S, O, CACHE_HASHES> {
+    /// Creates a new empty backend.
+    ///
+    /// Unlike [`Default::default`], this can be used in constant expressions.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            ends: Vec::new(),
+            hashes: Vec::new(),
+            buffer: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<I: Intern + ?Sized, S> StringBackend<I, S> {
+    /// Creates a backend from the given `buffer`, the `ends` of the strings in it,
+    /// and their `hashes`.
+    ///
+    /// # Safety
+    ///
+    /// `ends` must be monotonically increasing, the last end must equal `buffer.len()`,
+    /// every span must be a valid `I`, and `hashes` must have the same length as `ends`.
+    pub(crate) unsafe fn from_parts(
+        buffer: Vec<I::Primitive>,
+        ends: Vec<usize>,
+        hashes: Vec<u64>,
+    ) -> Self {
+        debug_assert_eq!(ends.len(), hashes.len());
+        Self {
+            ends,
+            hashes,
+            buffer,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the buffer of strings, the ends of every string in it, and their hashes.
+    pub(crate) fn as_parts(&self) -> (&[I::Primitive], &[usize], &[u64]) {
+        (&self.buffer, &self.ends, &self.hashes)
+    }
+
+    /// Returns the buffer of strings, the ends of every string in it, and their hashes.
+    pub(crate) fn into_parts(self) -> (Vec<I::Primitive>, Vec<usize>, Vec<u64>) {
+        (self.buffer, self.ends, self.hashes)
+    }
+}
+
+impl<I: Intern + ?Sized, S, O: Offset, const CACHE_HASHES: bool>
+    StringBackend<I, S, O, CACHE_HASHES>
+{
+    /// Reinterprets the stored strings as strings of type `J`, without copying them.
+    ///
+    /// # Safety
+    ///
+    /// Every stored string must be valid to be reinterpreted as `J` using [`Intern::from_bytes`].
+    pub(crate) unsafe fn cast<J>(self) -> StringBackend<J, S, O, CACHE_HASHES>
+    where
+        J: Intern<Primitive = I::Primitive> + ?Sized,
+    {
+        StringBackend {
+            ends: self.ends,
+            hashes: self.hashes,
+            buffer: self.buffer,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset, const CACHE_HASHES: bool>
+    StringBackend<I, S, O, CACHE_HASHES>
+{
+    /// Returns the string associated to the span.
+    ///
+    /// # Safety
+    ///
+    /// Span must be valid within the [Self::buffer]
+    unsafe fn span_to_str(&self, from: usize, to: usize) -> &I {
+        unsafe { I::from_bytes(&self.buffer[from..to]) }
+    }
+
+    #[inline]
+    pub(crate) fn iter_from(&self, index: usize) -> Iter<'_, I, S, O> {
+        Iter::new_from(&self.buffer, &self.ends, index)
+    }
+
+    #[inline]
+    pub(crate) fn buffer(&self) -> &[I::Primitive] {
+        &self.buffer
+    }
+
+    /// Returns the span of the string at `index` in the buffer if any.
+    #[inline]
+    pub(crate) fn span(&self, index: usize) -> Option<(usize, usize)> {
+        let to = self.ends.get(index)?.to_usize();
+        Some((self.start_of(index), to))
+    }
+
+    /// Returns the span of the string at `index` in the buffer without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be in bounds of [Self::ends].
+    #[inline]
+    pub(crate) unsafe fn span_unchecked(&self, index: usize) -> (usize, usize) {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let to = unsafe { self.ends.get_unchecked(index).to_usize() };
+        (self.start_of(index), to)
+    }
+
+    #[inline]
+    fn start_of(&self, index: usize) -> usize {
+        self.ends
+            .get(index.wrapping_sub(1))
+            .map(|end| end.to_usize())
+            .unwrap_or(0)
+    }
+}
+
+impl<S: Symbol, O: Offset, const CACHE_HASHES: bool> StringBackend<str, S, O, CACHE_HASHES> {
+    /// Appends the formatted `args` to the buffer, after the stored strings.
+    ///
+    /// The appended string is not stored until [`Self::try_store_pushed`] is called.
+    /// It must be followed by it or by [`Self::discard_pushed`] before the backend
+    /// is used otherwise, or the appended string becomes part of the next one.
+    /// If formatting panics, the appended part is removed again.
+    ///
+    /// # Panics
+    ///
+    /// If a formatting trait implementation returns an error.
+    pub(crate) fn push_fmt(&mut self, args: fmt::Arguments<'_>) {
+        let mut guard = self.push_guard();
+        fmt::Write::write_fmt(&mut guard, args)
+            .expect("a formatting trait implementation returned an error");
+        mem::forget(guard);
+    }
+
+    /// Appends the given `chars` to the buffer, after the stored strings.
+    ///
+    /// Same as [`Self::push_fmt`]. If the iterator panics, the appended part is removed again.
+    pub(crate) fn push_chars(&mut self, chars: impl IntoIterator<Item = char>) {
+        let guard = self.push_guard();
+        let chars = chars.into_iter();
+        guard.buffer.reserve(chars.size_hint().0);
+        for char in chars {
+            let mut bytes = [0; 4];
+            guard
+                .buffer
+                .extend_from_slice(char.encode_utf8(&mut bytes).as_bytes());
+        }
+        mem::forget(guard);
+    }
+}
+
+impl<S: Symbol, O: Offset, const CACHE_HASHES: bool> StringBackend<[u8], S, O, CACHE_HASHES> {
+    /// Appends the concatenation of the given `chunks` to the buffer, after the stored strings.
+    ///
+    /// Same as [`StringBackend::push_fmt`]. If the iterator panics,
+    /// the appended part is removed again.
+    pub(crate) fn push_chunks<'c>(&mut self, chunks: impl IntoIterator<Item = &'c [u8]>) {
+        let guard = self.push_guard();
+        for chunk in chunks {
+            guard.buffer.extend_from_slice(chunk);
+        }
+        mem::forget(guard);
+    }
+}
+
+impl<I, S, O, const CACHE_HASHES: bool> StringBackend<I, S, O, CACHE_HASHES>
+where
+    I: Intern<Primitive = u8> + ?Sized,
+    S: Symbol,
+    O: Offset,
+{
+    fn push_guard(&mut self) -> PushGuard<'_> {
+        debug_assert_eq!(self.buffer.len(), self.start_of(self.ends.len()));
+        PushGuard {
+            len: self.buffer.len(),
+            buffer: &mut self.buffer,
+        }
+    }
+
+    /// Returns the string appended with [`StringBackend::push_fmt`],
+    /// [`StringBackend::push_chars`] or [`StringBackend::push_chunks`].
+    pub(crate) fn pushed(&self) -> &I {
+        // SAFETY: Only whole `I`s are appended to the buffer after the stored strings.
+        unsafe { I::from_bytes(&self.buffer[self.start_of(self.ends.len())..]) }
+    }
+
+    /// Stores the appended string with the given `hash`.
+    ///
+    /// # Errors
+    ///
+    /// Same as for [`Backend::try_intern`]. The appended string is discarded in this case.
+    pub(crate) fn try_store_pushed(&mut self, hash: u64) -> Result<S, InternError> {
+        let Some(symbol) = S::try_from_usize(self.ends.len()) else {
+            self.discard_pushed();
+            return Err(InternError::SymbolOverflow);
+        };
+        let Some(to) = O::try_from_usize(self.buffer.len()) else {
+            self.discard_pushed();
+            return Err(InternError::OffsetOverflow);
+        };
+        self.ends.push(to);
+        if CACHE_HASHES {
+            self.hashes.push(hash);
+        }
+        Ok(symbol)
+    }
+
+    /// Removes the appended string.
+    pub(crate) fn discard_pushed(&mut self) {
+        self.buffer.truncate(self.start_of(self.ends.len()));
+    }
+}
+
+/// Truncates the buffer to `len` when dropped, unless it is forgotten.
+struct PushGuard<'a> {
+    buffer: &'a mut Vec<u8>,
+    len: usize,
+}
+
+impl fmt::Write for PushGuard<'_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.buffer.extend_from_slice(string.as_bytes());
+        Ok(())
+    }
+}
+
+impl Drop for PushGuard<'_> {
+    fn drop(&mut self) {
+        self.buffer.truncate(self.len);
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset> StringBackend<I, S, O> {
+    #[inline]
+    pub(crate) fn as_table(&self) -> ResolveTable<'_, I, S, O> {
+        // SAFETY: The backend upholds all invariants required by the table.
+        unsafe { ResolveTable::new(&self.buffer, &self.ends, &self.hashes) }
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset, const CACHE_HASHES: bool> Backend<I, S>
+    for StringBackend<I, S, O, CACHE_HASHES>
+{
+    type Iter<'a>
+        = Iter<'a, I, S, O>
+    where
+        Self: 'a,
+        I: 'a;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn with_capacity(cap: usize) -> Self {
+        // According to google the approx. word length is 5. So we will use 10.
+        const DEFAULT_WORD_LEN: usize = 10;
+        Self {
+            ends: Vec::with_capacity(cap),
+            hashes: Vec::with_capacity(if CACHE_HASHES { cap } else { 0 }),
+            buffer: Vec::with_capacity(cap * DEFAULT_WORD_LEN),
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn try_intern(&mut self, string: &I, hash: u64) -> Result<S, InternError> {
+        let symbol = S::try_from_usize(self.ends.len()).ok_or(InternError::SymbolOverflow)?;
+        let bytes = string.as_bytes();
+        let to = self
+            .buffer
+            .len()
+            .checked_add(bytes.len())
+            .and_then(O::try_from_usize)
+            .ok_or(InternError::OffsetOverflow)?;
+        self.buffer.extend_from_slice(bytes);
+        self.ends.push(to);
+        if CACHE_HASHES {
+            self.hashes.push(hash);
+        }
+        Ok(symbol)
+    }
+
+    #[inline]
+    fn resolve(&self, symbol: S) -> Option<&I> {
+        let index = symbol.to_usize();
+        let to = self.ends.get(index)?.to_usize();
+
+        let from = self
+            .ends
+            .get(index.wrapping_sub(1))
+            .map(|end| end.to_usize())
+            .unwrap_or(0);
+
+        // SAFETY: This span is guaranteed to be valid
+        unsafe { Some(self.span_to_str(from, to)) }
+    }
+
+    #[inline]
+    unsafe fn resolve_unchecked(&self, symbol: S) -> &I {
+        let index = symbol.to_usize();
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let to = unsafe { self.ends.get_unchecked(index).to_usize() };
+        let from = self
+            .ends
+            .get(index.wrapping_sub(1))
+            .map(|end| end.to_usize())
+            .unwrap_or(0);
+
+        // SAFETY: This span is guaranteed to be valid
+        unsafe { self.span_to_str(from, to) }
+    }
+
+    #[inline]
+    fn byte_len(&self, symbol: S) -> Option<usize> {
+        let (from, to) = self.span(symbol.to_usize())?;
+        Some(to - from)
+    }
+
+    #[inline]
+    unsafe fn byte_len_unchecked(&self, symbol: S) -> usize {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let (from, to) = unsafe { self.span_unchecked(symbol.to_usize()) };
+        to - from
+    }
+
+    #[inline]
+    unsafe fn hash_unchecked(&self, symbol: S, make_hash: impl FnOnce(&I) -> u64) -> u64 {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe {
+            if CACHE_HASHES {
+                *self.hashes.get_unchecked(symbol.to_usize())
+            } else {
+                make_hash(self.resolve_unchecked(symbol))
+            }
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.ends.capacity()
+    }
+
+    fn buffer_capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    fn buffer_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.ends.reserve(additional);
+        if CACHE_HASHES {
+            self.hashes.reserve(additional);
+        }
+    }
+
+    fn reserve_buffer(&mut self, additional: usize) {
+        self.buffer.reserve(additional);
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.ends.try_reserve(additional)?;
+        if CACHE_HASHES {
+            self.hashes.try_reserve(additional)?;
+        }
+        Ok(())
+    }
+
+    fn try_reserve_buffer(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buffer.try_reserve(additional)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.ends.shrink_to_fit();
+        self.hashes.shrink_to_fit();
+        self.buffer.shrink_to_fit();
+    }
+
+    fn shrink_to(&mut self, min_cap: usize, min_buffer_cap: usize) {
+        self.ends.shrink_to(min_cap);
+        self.hashes.shrink_to(min_cap);
+        self.buffer.shrink_to(min_buffer_cap);
+    }
+
+    fn clear(&mut self) {
+        self.ends.clear();
+        self.hashes.clear();
+        self.buffer.clear();
+    }
+
+    fn truncate(&mut self, len: usize) {
+        if len >= self.ends.len() {
+            return;
+        }
+        self.ends.truncate(len);
+        self.hashes.truncate(len);
+        self.buffer
+            .truncate(self.ends.last().map_or(0, |&end| end.to_usize()));
+    }
+
+    fn retain(&mut self, mut f: impl FnMut(S, &I) -> bool, _make_hash: impl FnMut(&I) -> u64) {
+        // Decide about all strings first, so that a panic in `f` leaves the backend unchanged.
+        let keep: Vec<bool> = self
+            .iter()
+            .map(|(symbol, string)| f(symbol, string))
+            .collect();
+        let mut len = 0;
+        let mut from = 0;
+        for (index, keep) in keep.into_iter().enumerate() {
+            let to = self.ends[index].to_usize();
+            if keep {
+                let start = self.start_of(len);
+                self.buffer.copy_within(from..to, start);
+                // The new end is at most the old one, so it fits into the offset.
+                self.ends[len] = O::try_from_usize(start + to - from).unwrap();
+                if CACHE_HASHES {
+                    self.hashes[len] = self.hashes[index];
+                }
+                len += 1;
+            }
+            from = to;
+        }
+        self.truncate(len);
+    }
+
+    fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            buffer_bytes: self.buffer.capacity() * mem::size_of::<I::Primitive>(),
+            ends_bytes: self.ends.capacity() * mem::size_of::<O>()
+                + self.hashes.capacity() * mem::size_of::<u64>(),
+            dedup_bytes: 0,
+        }
+    }
+
+    fn rehash(&mut self, mut make_hash: impl FnMut(&I) -> u64) {
+        let mut from = 0;
+        for (to, hash) in self.ends.iter().zip(&mut self.hashes) {
+            let to = to.to_usize();
+            // SAFETY: This span is guaranteed to be valid
+            *hash = make_hash(unsafe { I::from_bytes(&self.buffer[from..to]) });
+            from = to;
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> Iter<'_, I, S, O> {
+        Iter::new(&self.buffer, &self.ends)
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset> HashedBackend<I, S> for StringBackend<I, S, O> {
+    type IterWithHashes<'a>
+        = IterWithHashes<'a, I, S, O>
+    where
+        Self: 'a,
+        I: 'a;
+
+    fn get_hash(&self, symbol: S) -> Option<u64> {
+        self.hashes.get(symbol.to_usize()).copied()
+    }
+
+    unsafe fn get_hash_unchecked(&self, symbol: S) -> u64 {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { *self.hashes.get_unchecked(symbol.to_usize()) }
+    }
+
+    #[inline]
+    fn resolve_with_hash(&self, symbol: S) -> Option<(&I, u64)> {
+        let &hash = self.hashes.get(symbol.to_usize())?;
+        // SAFETY: Every stored string has a hash, so the symbol is valid.
+        Some((unsafe { self.resolve_unchecked(symbol) }, hash))
+    }
+
+    #[inline]
+    fn iter_with_hashes(&self) -> IterWithHashes<'_, I, S, O> {
+        IterWithHashes::new(&self.buffer, &self.ends, &self.hashes)
+    }
+
+    #[inline]
+    fn hashes<'a>(&'a self) -> impl ExactSizeIterator<Item = u64> + FusedIterator + 'a
+    where
+        I: 'a,
+        S: 'a,
+    {
+        self.hashes.iter().copied()
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, O: Offset, const CACHE_HASHES: bool> IntoIterator
+    for &'a StringBackend<I, S, O, CACHE_HASHES>
+{
+    type Item = (S, &'a I);
+    type IntoIter = Iter<'a, I, S, O>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the interned symbols and their strings
+pub struct Iter<'a, I: Intern + ?Sized, S, O = usize> {
+    buffer: &'a [I::Primitive],
+    start: usize,
+    index: usize,
+    ends: slice::Iter<'a, O>,
+    marker: PhantomData<fn() -> (S, &'a I)>,
+}
+
+impl<'a, I: Intern + ?Sized, S, O: Offset> Iter<'a, I, S, O> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(crate) fn new(buffer: &'a [I::Primitive], ends: &'a [O]) -> Self {
+        Self::new_from(buffer, ends, 0)
+    }
+
+    /// Creates an iterator that starts at the entry with the given `index`.
+    ///
+    /// The iterator is empty if `index` is out of bounds.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(crate) fn new_from(buffer: &'a [I::Primitive], ends: &'a [O], index: usize) -> Self {
+        let index = index.min(ends.len());
+        let start = ends
+            .get(index.wrapping_sub(1))
+            .map(|end| end.to_usize())
+            .unwrap_or(0);
+        Self {
+            buffer,
+            start,
+            index,
+            ends: ends[index..].iter(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries that are yet to be yielded.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns the index of the symbol that will be yielded next.
+    #[inline]
+    pub fn current_index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, O: Offset> Iterator for Iter<'a, I, S, O> {
+    type Item = (S, &'a I);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ends.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let to = self.ends.next()?.to_usize();
+        let from = core::mem::replace(&mut self.start, to);
+        let id = self.index;
+        self.index += 1;
+
+        // SAFETY: This span is guaranteed to be valid
+        let string = unsafe { I::from_bytes(&self.buffer[from..to]) };
+
+        Some((expect_valid_symbol(id), string))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let skip = n.min(self.remaining());
+        // Skipping the ends of a slice takes constant time,
+        // and only the end of the last skipped string is needed.
+        if let Some(end) = skip.checked_sub(1).and_then(|last| self.ends.nth(last)) {
+            self.start = end.to_usize();
+            self.index += skip;
+        }
+        self.next()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.nth(self.remaining().checked_sub(1)?)
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset> ExactSizeIterator for Iter<'_, I, S, O> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset> FusedIterator for Iter<'_, I, S, O> {}
+
+/// An iterator over the interned symbols, their strings, and their hashes.
+pub struct IterWithHashes<'a, I: Intern + ?Sized, S, O = usize> {
+    inner: Iter<'a, I, S, O>,
+    hashes: slice::Iter<'a, u64>,
+}
+
+impl<'a, I: Intern + ?Sized, S, O: Offset> IterWithHashes<'a, I, S, O> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub(crate) fn new(buffer: &'a [I::Primitive], ends: &'a [O], hashes: &'a [u64]) -> Self {
+        debug_assert_eq!(ends.len(), hashes.len());
+        Self {
+            inner: Iter::new(buffer, ends),
+            hashes: hashes.iter(),
+        }
+    }
+
+    /// Returns the number of entries that are yet to be yielded.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    /// Returns the index of the symbol that will be yielded next.
+    #[inline]
+    pub fn current_index(&self) -> usize {
+        self.inner.current_index()
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, O: Offset> Iterator for IterWithHashes<'a, I, S, O> {
+    type Item = (S, &'a I, u64);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (symbol, string) = self.inner.next()?;
+        let &hash = self.hashes.next()?;
+        Some((symbol, string, hash))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let hash = self.hashes.nth(n);
+        let (symbol, string) = self.inner.nth(n)?;
+        Some((symbol, string, *hash?))
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.nth(self.remaining().checked_sub(1)?)
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset> ExactSizeIterator for IterWithHashes<'_, I, S, O> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset> FusedIterator for IterWithHashes<'_, I, S, O> {}
@@ -0,0 +1,129 @@
+use crate::{intern::Intern, DefaultSymbol, Interner, Symbol};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug, Formatter},
+    hash::BuildHasher,
+    marker::PhantomData,
+};
+use hashbrown::DefaultHashBuilder;
+
+/// Builder for an [`Interner`] that deduplicates all strings at once.
+///
+/// [`InternerBuilder::push`] only appends strings to a buffer, without hashing or
+/// deduplicating them. [`InternerBuilder::build_deduplicated`] then hashes every string
+/// and removes the duplicates in a single pass, reusing the same buffer.
+///
+/// This avoids growing the deduplication map of the [`Interner`] incrementally,
+/// and is meant for one-shot use cases where all strings are known upfront
+/// and most of them are unique. On the benchmark corpus of 100k unique strings of 5 bytes,
+/// it is about 40% faster than calling [`Interner::intern`] for every string.
+///
+/// # Example
+///
+/// ```
+/// # use string_hash_interner::{DefaultStringInterner, InternerBuilder};
+/// let mut builder = InternerBuilder::<str>::new();
+/// for s in ["Earth", "Water", "Earth", "Fire"] {
+///     builder.push(s);
+/// }
+/// let (interner, symbols): (DefaultStringInterner, _) = builder.build_deduplicated();
+///
+/// assert_eq!(interner.len(), 3);
+/// assert_eq!(symbols[0], symbols[2]);
+/// assert_eq!(interner.resolve(symbols[3]), Some("Fire"));
+/// ```
+pub struct InternerBuilder<I: Intern + ?Sized, S = DefaultSymbol, H = DefaultHashBuilder> {
+    buffer: Vec<I::Primitive>,
+    ends: Vec<usize>,
+    hasher: H,
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<I: Intern + ?Sized, S, H> Debug for InternerBuilder<I, S, H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InternerBuilder")
+            .field("ends", &self.ends)
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default> Default for InternerBuilder<I, S, H> {
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default> InternerBuilder<I, S, H> {
+    /// Creates a new empty [`InternerBuilder`].
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn new() -> Self {
+        Self::with_hasher(H::default())
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher> InternerBuilder<I, S, H> {
+    /// Creates a new empty [`InternerBuilder`] with the given hasher.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_hasher(hash_builder: H) -> Self {
+        Self {
+            buffer: Vec::new(),
+            ends: Vec::new(),
+            hasher: hash_builder,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of pushed strings, including duplicates.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if no strings were pushed.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the given string without deduplicating it.
+    ///
+    /// Returns the index of the string, which can be used with the symbol table
+    /// returned by [`InternerBuilder::build_deduplicated`].
+    #[inline]
+    pub fn push<T: AsRef<I>>(&mut self, string: T) -> usize {
+        self.buffer.extend_from_slice(string.as_ref().as_bytes());
+        self.ends.push(self.buffer.len());
+        self.ends.len() - 1
+    }
+
+    /// Deduplicates the pushed strings and builds the [`Interner`].
+    ///
+    /// Returns the interner, and the symbols of the pushed strings in push order:
+    /// the symbol of the string returned by the `n`-th call to [`InternerBuilder::push`]
+    /// is at index `n`. Symbols are assigned in order of first occurrence, so the result
+    /// is the same as interning all pushed strings one by one.
+    ///
+    /// # Panics
+    ///
+    /// If the number of unique strings exceeds the maximum number of strings possible
+    /// by the chosen symbol type.
+    pub fn build_deduplicated(self) -> (Interner<I, S, H>, Vec<S>) {
+        // SAFETY: `ends` were produced by pushing valid strings to the `buffer`.
+        unsafe { Interner::from_pushed(self.buffer, &self.ends, self.hasher) }
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher, T: AsRef<I>> Extend<T>
+    for InternerBuilder<I, S, H>
+{
+    fn extend<It>(&mut self, iter: It)
+    where
+        It: IntoIterator<Item = T>,
+    {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::intern::{FromPrimitives, Intern};
+use alloc::string::String;
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// A [`str`] that is hashed and compared ignoring ASCII case.
+///
+/// Interning it with [`CaseInsensitiveInterner`](crate::CaseInsensitiveInterner) maps
+/// strings that differ only in ASCII case to the same symbol,
+/// while resolving to the casing that was interned first.
+///
+/// # Example
+///
+/// ```
+/// use string_hash_interner::CaseInsensitiveInterner;
+///
+/// let mut interner = <CaseInsensitiveInterner>::new();
+/// let a = interner.intern("Content-Type");
+/// let b = interner.intern("content-type");
+/// assert_eq!(a, b);
+/// assert_eq!(interner.resolve(b).unwrap().as_str(), "Content-Type");
+/// ```
+#[repr(transparent)]
+pub struct CaseInsensitiveStr(str);
+
+impl CaseInsensitiveStr {
+    /// Wraps the given string.
+    #[inline]
+    pub fn new(string: &str) -> &Self {
+        // SAFETY: `CaseInsensitiveStr` is a `repr(transparent)` wrapper around `str`.
+        unsafe { &*(string as *const str as *const Self) }
+    }
+
+    /// Returns the string with its original casing.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for CaseInsensitiveStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for CaseInsensitiveStr {}
+
+impl Hash for CaseInsensitiveStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        /// Strings are lowercased in chunks of this length on the stack.
+        const CHUNK_LEN: usize = 32;
+
+        let mut buffer = [0; CHUNK_LEN];
+        for chunk in self.0.as_bytes().chunks(CHUNK_LEN) {
+            let lower = &mut buffer[..chunk.len()];
+            lower.copy_from_slice(chunk);
+            lower.make_ascii_lowercase();
+            state.write(lower);
+        }
+        // Same terminator as `str` uses, so that prefixes hash differently.
+        state.write_u8(0xff);
+    }
+}
+
+impl fmt::Debug for CaseInsensitiveStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for CaseInsensitiveStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl AsRef<CaseInsensitiveStr> for CaseInsensitiveStr {
+    #[inline]
+    fn as_ref(&self) -> &CaseInsensitiveStr {
+        self
+    }
+}
+
+impl AsRef<CaseInsensitiveStr> for str {
+    #[inline]
+    fn as_ref(&self) -> &CaseInsensitiveStr {
+        CaseInsensitiveStr::new(self)
+    }
+}
+
+impl AsRef<CaseInsensitiveStr> for String {
+    #[inline]
+    fn as_ref(&self) -> &CaseInsensitiveStr {
+        CaseInsensitiveStr::new(self)
+    }
+}
+
+unsafe impl Intern for CaseInsensitiveStr {
+    type Primitive = u8;
+
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    unsafe fn from_bytes(bytes: &[u8]) -> &Self {
+        // SAFETY: Calling this function is only valid with bytes obtained from `Self::as_bytes`.
+        Self::new(unsafe { core::str::from_utf8_unchecked(bytes) })
+    }
+}
+
+impl FromPrimitives for CaseInsensitiveStr {
+    fn from_bytes_checked(bytes: &[u8]) -> Option<&Self> {
+        core::str::from_utf8(bytes).ok().map(Self::new)
+    }
+}
@@ -0,0 +1,40 @@
+/// A point in the history of an [`Interner`](crate::Interner) that it can be rolled back to.
+///
+/// Created with [`Interner::checkpoint`](crate::Interner::checkpoint) and consumed by
+/// [`Interner::rollback`](crate::Interner::rollback), which removes every string
+/// interned after the checkpoint was taken.
+///
+/// A checkpoint only records the number of strings, so it is only meaningful for the
+/// interner it was taken from, as long as no strings were removed from it since.
+/// In debug builds, it also records how many times strings were removed, so that
+/// [`Interner::rollback`](crate::Interner::rollback) panics on a stale checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[must_use]
+pub struct Checkpoint {
+    count: usize,
+    #[cfg(debug_assertions)]
+    generation: usize,
+}
+
+impl Checkpoint {
+    #[inline]
+    pub(crate) fn new(count: usize, #[cfg(debug_assertions)] generation: usize) -> Self {
+        Self {
+            count,
+            #[cfg(debug_assertions)]
+            generation,
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[inline]
+    pub(crate) fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Returns the number of strings the interner contained when the checkpoint was taken.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{
+    backend::BucketBackend, intern::Intern, DefaultHashBuilder, DefaultSymbol, Entry, InternError,
+    Interner, Symbol,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt::{self, Debug, Formatter},
+    hash::BuildHasher,
+    num::NonZero,
+};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A shard of a [`ConcurrentInterner`].
+type Shard<I, S, H> = Interner<I, S, H, BucketBackend<I, S>>;
+
+/// An interner which can intern strings from many threads at once.
+///
+/// The strings are distributed over a number of shards by their hashes,
+/// and every shard is an [`Interner`] guarded by its own [`Mutex`].
+/// Threads interning strings which fall into different shards don't contend.
+///
+/// Every shard stores its strings in a [`BucketBackend`], which never moves them,
+/// so [`ConcurrentInterner::resolve`] returns references which stay valid
+/// after the lock of the shard is released.
+///
+/// # Symbols
+///
+/// The lowest bits of the index of every symbol select the shard,
+/// and the remaining bits are the index of the string in the shard.
+/// So the symbols are not consecutive, and a symbol type may overflow before
+/// it holds as many strings as it would in an [`Interner`]: a shard that is fuller
+/// than the others runs out of indices first.
+///
+/// # Example
+///
+/// ```
+/// use std::thread;
+/// use string_hash_interner::ConcurrentInterner;
+///
+/// let interner = ConcurrentInterner::<str>::new();
+/// let symbols = thread::scope(|scope| {
+///     let handles: Vec<_> = (0..4)
+///         .map(|_| scope.spawn(|| interner.intern("shared")))
+///         .collect();
+///     handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+/// });
+/// assert!(symbols.iter().all(|&symbol| symbol == symbols[0]));
+/// assert_eq!(interner.resolve(symbols[0]), Some("shared"));
+/// ```
+pub struct ConcurrentInterner<I: Intern + ?Sized, S: Symbol = DefaultSymbol, H = DefaultHashBuilder>
+{
+    shards: Box<[Mutex<Shard<I, S, H>>]>,
+    /// The number of bits of a symbol which select the shard.
+    shard_bits: u32,
+    hasher: H,
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Clone + Default> ConcurrentInterner<I, S, H> {
+    /// Creates a new empty [`ConcurrentInterner`] with the default number of shards,
+    /// which is four times the available parallelism rounded up to a power of two.
+    pub fn new() -> Self {
+        let parallelism = std::thread::available_parallelism().map_or(1, NonZero::get);
+        Self::with_shards(parallelism * 4)
+    }
+
+    /// Creates a new empty [`ConcurrentInterner`] with at least `shards` shards.
+    ///
+    /// The number of shards is rounded up to a power of two.
+    pub fn with_shards(shards: usize) -> Self {
+        Self::with_shards_and_hasher(shards, H::default())
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Clone> ConcurrentInterner<I, S, H> {
+    /// Creates a new empty [`ConcurrentInterner`] with at least `shards` shards
+    /// and the given `hasher`.
+    ///
+    /// The number of shards is rounded up to a power of two.
+    pub fn with_shards_and_hasher(shards: usize, hasher: H) -> Self {
+        let shards = shards.max(1).next_power_of_two();
+        Self {
+            shards: (0..shards)
+                .map(|_| Mutex::new(Interner::with_hasher(hasher.clone())))
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+            shard_bits: shards.trailing_zeros(),
+            hasher,
+        }
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher> ConcurrentInterner<I, S, H> {
+    /// Returns the number of shards.
+    #[inline]
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the hasher of the interner.
+    #[inline]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Returns the number of interned strings.
+    ///
+    /// Locks every shard in turn, so strings interned concurrently
+    /// may or may not be counted.
+    pub fn len(&self) -> usize {
+        (0..self.shards.len())
+            .map(|index| self.lock(index).len())
+            .sum()
+    }
+
+    /// Returns `true` if the interner has no interned strings.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Interns the given string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Panics
+    ///
+    /// If the shard of the string already interns the maximum number of strings
+    /// possible by the chosen symbol type.
+    #[inline]
+    pub fn intern<T: AsRef<I>>(&self, string: T) -> S {
+        self.try_intern(string).expect("encountered invalid symbol")
+    }
+
+    /// Interns the given string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// # Errors
+    ///
+    /// If the shard of the string already interns the maximum number of strings
+    /// possible by the chosen symbol type. The interner is left unchanged in this case.
+    pub fn try_intern<T: AsRef<I>>(&self, string: T) -> Result<S, InternError> {
+        let string = string.as_ref();
+        let hash = self.hasher.hash_one(string);
+        let index = self.shard_of(hash);
+        let mut shard = self.lock(index);
+        let next = shard.len();
+        match shard.entry_hashed(string, hash) {
+            Entry::Occupied(local) => self.to_global(index, local.to_usize()),
+            Entry::Vacant(vacant) => {
+                // Check that the symbol fits before the string is interned.
+                let symbol = self.to_global(index, next)?;
+                vacant.try_intern()?;
+                Ok(symbol)
+            }
+        }
+    }
+
+    /// Returns the symbol for the given string if any.
+    pub fn get<T: AsRef<I>>(&self, string: T) -> Option<S> {
+        let string = string.as_ref();
+        let hash = self.hasher.hash_one(string);
+        let index = self.shard_of(hash);
+        let local = self.lock(index).get_hashed(string, hash)?;
+        self.to_global(index, local.to_usize()).ok()
+    }
+
+    /// Returns `true` if the interner contains the given string.
+    #[inline]
+    pub fn contains<T: AsRef<I>>(&self, string: T) -> bool {
+        let string = string.as_ref();
+        let hash = self.hasher.hash_one(string);
+        self.lock(self.shard_of(hash)).contains_hashed(string, hash)
+    }
+
+    /// Returns the string for the given `symbol` if any.
+    ///
+    /// The returned reference stays valid while other threads intern more strings.
+    pub fn resolve(&self, symbol: S) -> Option<&I> {
+        let index = symbol.to_usize();
+        let shard = index & (self.shards.len() - 1);
+        let local = S::try_from_usize(index >> self.shard_bits)?;
+        let string: *const I = self.lock(shard).resolve(local)?;
+        // SAFETY: The `BucketBackend` of the shard never moves its strings,
+        //         and strings are only removed with a mutable reference to `self`.
+        Some(unsafe { &*string })
+    }
+
+    /// Removes all interned strings, keeping the allocated memory for reuse.
+    ///
+    /// Symbols obtained before the call are invalidated.
+    pub fn clear(&mut self) {
+        for shard in &mut self.shards {
+            shard
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
+    }
+
+    /// Returns the index of the shard of the string with the given `hash`.
+    #[inline]
+    fn shard_of(&self, hash: u64) -> usize {
+        // The lowest and the highest bits are used by the deduplication map of the shard.
+        (hash >> 32) as usize & (self.shards.len() - 1)
+    }
+
+    /// Returns the symbol of the string at `local` index in the shard at `index`.
+    #[inline]
+    fn to_global(&self, index: usize, local: usize) -> Result<S, InternError> {
+        local
+            .checked_mul(self.shards.len())
+            .and_then(|global| S::try_from_usize(global | index))
+            .ok_or(InternError::SymbolOverflow)
+    }
+
+    #[inline]
+    fn lock(&self, index: usize) -> MutexGuard<'_, Shard<I, S, H>> {
+        // Interning leaves the shard consistent even if it panics, so poisoning is ignored.
+        self.shards[index]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Clone + Default> Default
+    for ConcurrentInterner<I, S, H>
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H> Debug for ConcurrentInterner<I, S, H>
+where
+    I: Debug,
+    H: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrentInterner")
+            .field("shards", &self.shards)
+            .finish()
+    }
+}
+
+/// Declares a module with a lazily created, process-wide [`ConcurrentInterner`].
+///
+/// `global_interner!(pub names: str)` expands to a module `names` with the functions
+/// `intern`, `get`, `resolve` and `interner`, which all use the same interner
+/// of [`DefaultSymbol`]s. It is created on first use, so declaring it costs nothing.
+///
+/// # Locking
+///
+/// Interning and looking up a string lock only the shard of the string, see
+/// [`ConcurrentInterner`], so threads rarely wait for each other. `resolve` returns
+/// a `&'static` reference, because the strings of the interner are never moved
+/// or removed, and no lock is held while the string is in use.
+///
+/// # Example
+///
+/// ```
+/// string_hash_interner::global_interner!(pub names: str);
+///
+/// let hello = names::intern("hello");
+/// let resolved: &'static str = names::resolve(hello).unwrap();
+/// assert_eq!(resolved, "hello");
+/// assert_eq!(std::thread::spawn(|| names::get("hello")).join().unwrap(), Some(hello));
+/// ```
+///
+/// [`DefaultSymbol`]: crate::DefaultSymbol
+#[macro_export]
+macro_rules! global_interner {
+    ($vis:vis $name:ident : $ty:ty) => {
+        $vis mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            static INTERNER: $crate::__private::OnceLock<$crate::ConcurrentInterner<$ty>> =
+                $crate::__private::OnceLock::new();
+
+            /// Returns the interner, creating it on first use.
+            pub fn interner() -> &'static $crate::ConcurrentInterner<$ty> {
+                INTERNER.get_or_init(::core::default::Default::default)
+            }
+
+            /// Interns the given string.
+            ///
+            /// See [`ConcurrentInterner::intern`]($crate::ConcurrentInterner::intern).
+            pub fn intern(string: &$ty) -> $crate::DefaultSymbol {
+                interner().intern(string)
+            }
+
+            /// Returns the symbol for the given string if any.
+            pub fn get(string: &$ty) -> ::core::option::Option<$crate::DefaultSymbol> {
+                interner().get(string)
+            }
+
+            /// Returns the string for the given `symbol` if any.
+            pub fn resolve(symbol: $crate::DefaultSymbol) -> ::core::option::Option<&'static $ty> {
+                interner().resolve(symbol)
+            }
+        }
+    };
+}
@@ -0,0 +1,113 @@
+use crate::{backend::Backend, intern::Intern, InternError, Symbol};
+use core::fmt::{self, Debug, Formatter};
+use hashbrown::hash_table;
+
+/// A view into a single string of an [`Interner`](crate::Interner),
+/// which may be either interned or not.
+///
+/// Created by [`Interner::entry`](crate::Interner::entry).
+pub enum Entry<'a, I: Intern + ?Sized, S: Symbol, B: Backend<I, S>> {
+    /// The string is already interned with the given symbol.
+    Occupied(S),
+    /// The string is not interned yet.
+    Vacant(VacantEntry<'a, I, S, B>),
+}
+
+impl<I: Intern + ?Sized, S: Symbol + Debug, B: Backend<I, S>> Debug for Entry<'_, I, S, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Occupied(symbol) => f.debug_tuple("Occupied").field(symbol).finish(),
+            Self::Vacant(vacant) => f.debug_tuple("Vacant").field(vacant).finish(),
+        }
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, B: Backend<I, S>> Entry<'a, I, S, B> {
+    /// Returns the symbol of the string if it is already interned.
+    #[inline]
+    pub fn symbol(&self) -> Option<S> {
+        match self {
+            Self::Occupied(symbol) => Some(*symbol),
+            Self::Vacant(_) => None,
+        }
+    }
+
+    /// Interns the string if it is not interned yet, and returns its symbol.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn or_intern(self) -> S {
+        match self {
+            Self::Occupied(symbol) => symbol,
+            Self::Vacant(vacant) => vacant.intern(),
+        }
+    }
+}
+
+/// A view into a string that is not interned yet.
+///
+/// Part of the [`Entry`] enum.
+pub struct VacantEntry<'a, I: Intern + ?Sized, S: Symbol, B: Backend<I, S>> {
+    pub(crate) entry: hash_table::VacantEntry<'a, S>,
+    pub(crate) backend: &'a mut B,
+    pub(crate) string: &'a I,
+    pub(crate) hash: u64,
+}
+
+impl<I: Intern + ?Sized, S: Symbol, B: Backend<I, S>> Debug for VacantEntry<'_, I, S, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VacantEntry")
+            .field("hash", &self.hash)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, B: Backend<I, S>> VacantEntry<'a, I, S, B> {
+    /// Returns the string of the entry.
+    #[inline]
+    pub fn string(&self) -> &'a I {
+        self.string
+    }
+
+    /// Returns the hash of the string of the entry.
+    #[inline]
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Interns the string and returns its symbol.
+    ///
+    /// # Errors
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type. The interner is left unchanged in this case.
+    #[inline]
+    pub fn try_intern(self) -> Result<S, InternError> {
+        let symbol = self.backend.try_intern(self.string, self.hash)?;
+        self.entry.insert(symbol);
+        Ok(symbol)
+    }
+
+    /// Interns the string, which is the `'static` string given here, and returns its symbol.
+    #[inline]
+    pub(crate) fn try_intern_static(self, string: &'static I) -> Result<S, InternError> {
+        debug_assert!(core::ptr::eq(self.string, string));
+        let symbol = self.backend.try_intern_static(string, self.hash)?;
+        self.entry.insert(symbol);
+        Ok(symbol)
+    }
+
+    /// Interns the string and returns its symbol.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn intern(self) -> S {
+        self.try_intern().expect("encountered invalid symbol")
+    }
+}
@@ -0,0 +1,93 @@
+use alloc::collections::TryReserveError;
+use core::fmt;
+
+/// Errors that can occur while interning a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InternError {
+    /// The interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    SymbolOverflow,
+    /// The string doesn't fit into the buffer, because its end can't be
+    /// represented by the chosen offset type.
+    OffsetOverflow,
+}
+
+impl fmt::Display for InternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SymbolOverflow => f.write_str("symbol type cannot represent any more strings"),
+            Self::OffsetOverflow => {
+                f.write_str("offset type cannot represent the end of the string")
+            }
+        }
+    }
+}
+
+impl core::error::Error for InternError {}
+
+/// Errors that can occur while reserving capacity in an [`Interner`](crate::Interner).
+///
+/// The error of the deduplication map is kept as is, because the error type of `alloc`
+/// can't be constructed outside of the standard library without repeating
+/// the failed allocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReserveError {
+    /// Reserving capacity in the deduplication map failed.
+    Dedup(hashbrown::TryReserveError),
+    /// Reserving capacity in the backend failed.
+    Backend(TryReserveError),
+}
+
+impl fmt::Display for ReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dedup(hashbrown::TryReserveError::CapacityOverflow) => {
+                f.write_str("capacity of the deduplication map overflowed")
+            }
+            Self::Dedup(hashbrown::TryReserveError::AllocError { layout }) => write!(
+                f,
+                "memory allocation of {} bytes for the deduplication map failed",
+                layout.size()
+            ),
+            Self::Backend(error) => write!(f, "reserving capacity in the backend failed: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for ReserveError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Dedup(_) => None,
+            Self::Backend(error) => Some(error),
+        }
+    }
+}
+
+/// Errors that can occur while resolving a symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ResolveError {
+    /// The index of the symbol is not below the number of strings in the interner,
+    /// usually because the symbol was obtained from another interner.
+    OutOfRange {
+        /// The index of the symbol.
+        index: usize,
+        /// The number of strings in the interner.
+        len: usize,
+    },
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange { index, len } => write!(
+                f,
+                "symbol index {index} is out of range for an interner of {len} strings"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ResolveError {}
@@ -0,0 +1,172 @@
+use crate::{
+    backend::{Backend, HashedBackend, StringBackend},
+    intern::Intern,
+    DefaultHashBuilder, DefaultSymbol, Interner, Symbol,
+};
+use core::{
+    fmt::{self, Debug, Formatter},
+    hash::BuildHasher,
+    ops::Index,
+};
+
+/// An [`Interner`] that can no longer intern new strings.
+///
+/// Created with [`Interner::freeze`]. It supports resolution and look-ups of strings,
+/// which only need shared references, so it can be put into an `Arc` and queried
+/// from many threads without any locking.
+///
+/// Freezing does not shrink the interner, call [`Interner::shrink_to_fit`] before
+/// to release the unused capacity.
+///
+/// # Example
+///
+/// ```
+/// use std::{sync::Arc, thread};
+/// use string_hash_interner::DefaultStringInterner;
+///
+/// let mut interner = DefaultStringInterner::new();
+/// let hello = interner.intern("hello");
+/// let frozen = Arc::new(interner.freeze());
+///
+/// let handle = {
+///     let frozen = Arc::clone(&frozen);
+///     thread::spawn(move || frozen.get("hello"))
+/// };
+/// assert_eq!(handle.join().unwrap(), Some(hello));
+/// assert_eq!(frozen.resolve(hello), Some("hello"));
+/// ```
+pub struct FrozenInterner<
+    I: Intern + ?Sized,
+    S: Symbol = DefaultSymbol,
+    H = DefaultHashBuilder,
+    B: Backend<I, S> = StringBackend<I, S>,
+> {
+    inner: Interner<I, S, H, B>,
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H, B: Backend<I, S>> Debug for FrozenInterner<I, S, H, B>
+where
+    I: Debug,
+    H: BuildHasher,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("FrozenInterner").field(&self.inner).finish()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: Clone, B: Backend<I, S> + Clone> Clone
+    for FrozenInterner<I, S, H, B>
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher, B: Backend<I, S>> FrozenInterner<I, S, H, B> {
+    #[inline]
+    pub(crate) fn new(inner: Interner<I, S, H, B>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the number of strings in the interner.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the interner has no strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the string for the given `symbol` if any.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&I> {
+        self.inner.resolve(symbol)
+    }
+
+    /// Returns the string for the given `symbol` without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the interner.
+    #[inline]
+    pub unsafe fn resolve_unchecked(&self, symbol: S) -> &I {
+        unsafe { self.inner.resolve_unchecked(symbol) }
+    }
+
+    /// Returns an iterator that yields all strings and their symbols.
+    #[inline]
+    pub fn iter(&self) -> B::Iter<'_> {
+        self.inner.iter()
+    }
+
+    /// Returns the interner, making it possible to intern strings again.
+    #[inline]
+    pub fn thaw(self) -> Interner<I, S, H, B> {
+        self.inner
+    }
+
+    /// Returns the symbol for the given string if any.
+    #[inline]
+    pub fn get<T: AsRef<I>>(&self, string: T) -> Option<S> {
+        self.inner.get(string)
+    }
+
+    /// Returns `true` if the interner contains the given string.
+    #[inline]
+    pub fn contains<T: AsRef<I>>(&self, string: T) -> bool {
+        self.inner.contains(string)
+    }
+
+    /// Returns `true` if the given `symbol` resolves to a string of the interner.
+    ///
+    /// See [`Interner::contains_symbol`].
+    #[inline]
+    pub fn contains_symbol(&self, symbol: S) -> bool {
+        self.inner.contains_symbol(symbol)
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H, B: HashedBackend<I, S>> FrozenInterner<I, S, H, B> {
+    /// Returns cached hash of the string for the given `symbol`.
+    #[inline]
+    pub fn get_hash(&self, symbol: S) -> Option<u64> {
+        self.inner.get_hash(symbol)
+    }
+
+    /// Returns the string for the given `symbol` and its cached hash if any.
+    #[inline]
+    pub fn resolve_with_hash(&self, symbol: S) -> Option<(&I, u64)> {
+        self.inner.resolve_with_hash(symbol)
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H, B: Backend<I, S>> Index<S> for FrozenInterner<I, S, H, B> {
+    type Output = I;
+
+    #[inline]
+    fn index(&self, symbol: S) -> &Self::Output {
+        &self.inner[symbol]
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, H, B: Backend<I, S>> IntoIterator
+    for &'a FrozenInterner<I, S, H, B>
+{
+    type Item = (S, &'a I);
+    type IntoIter = B::Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        (&self.inner).into_iter()
+    }
+}
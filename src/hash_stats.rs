@@ -0,0 +1,31 @@
+/// Statistics about the hashes of the strings in an [`Interner`](crate::Interner).
+///
+/// Returned by [`Interner::hash_stats`](crate::Interner::hash_stats).
+/// Only full 64-bit hashes that are equal count as collisions. The deduplication map
+/// uses only some bits of every hash to find the slot of a string, so it may still have
+/// to compare strings with different hashes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HashStats {
+    /// The number of different hashes.
+    pub distinct_hashes: usize,
+    /// The number of strings whose hash is the same as the hash of another string.
+    pub collisions: usize,
+    /// The largest number of strings with the same hash, or `0` if there are no strings.
+    pub max_chain_len: usize,
+}
+
+impl HashStats {
+    /// Computes the statistics of the given hashes, sorting them in place.
+    pub(crate) fn from_hashes(hashes: &mut [u64]) -> Self {
+        hashes.sort_unstable();
+        let mut stats = Self::default();
+        for chain in hashes.chunk_by(|a, b| a == b) {
+            stats.distinct_hashes += 1;
+            if chain.len() > 1 {
+                stats.collisions += chain.len();
+            }
+            stats.max_chain_len = stats.max_chain_len.max(chain.len());
+        }
+        stats
+    }
+}
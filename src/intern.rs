@@ -11,7 +11,7 @@ use core::{ffi::CStr, fmt::Debug, hash::Hash};
 /// as `&Self` using [`Intern::from_bytes`]. Even if they were moved in memory.
 pub unsafe trait Intern: Hash + PartialEq + Eq {
     /// A primitive type that has the same alignment as `Self`.
-    type Primitive: Sized + Copy + Debug;
+    type Primitive: Sized + Copy + Debug + 'static;
 
     fn as_bytes(&self) -> &[Self::Primitive];
 
@@ -21,6 +21,16 @@ pub unsafe trait Intern: Hash + PartialEq + Eq {
     unsafe fn from_bytes(bytes: &[Self::Primitive]) -> &Self;
 }
 
+/// Trait implemented by string types that can be validated when created from
+/// their primitives.
+///
+/// Used where the primitives come from an untrusted source,
+/// like in the compact serialization format.
+pub trait FromPrimitives: Intern {
+    /// Returns the string made of the given `bytes`, or `None` if they don't form a valid `Self`.
+    fn from_bytes_checked(bytes: &[Self::Primitive]) -> Option<&Self>;
+}
+
 unsafe impl Intern for str {
     type Primitive = u8;
 
@@ -34,6 +44,12 @@ unsafe impl Intern for str {
     }
 }
 
+impl FromPrimitives for str {
+    fn from_bytes_checked(bytes: &[u8]) -> Option<&Self> {
+        core::str::from_utf8(bytes).ok()
+    }
+}
+
 unsafe impl Intern for CStr {
     type Primitive = u8;
 
@@ -47,34 +63,52 @@ unsafe impl Intern for CStr {
     }
 }
 
-unsafe impl Intern for [u8] {
-    type Primitive = u8;
-
-    fn as_bytes(&self) -> &[u8] {
-        self
-    }
-
-    unsafe fn from_bytes(bytes: &[u8]) -> &Self {
-        bytes
+impl FromPrimitives for CStr {
+    fn from_bytes_checked(bytes: &[u8]) -> Option<&Self> {
+        CStr::from_bytes_with_nul(bytes).ok()
     }
 }
 
-unsafe impl Intern for [char] {
-    type Primitive = char;
+/// Slices of any plain values can be interned, like `[u8]`, `[char]` or `[u32]`.
+///
+/// The slice is its own primitive representation, so both conversions are the identity.
+unsafe impl<T: Copy + Hash + Eq + Debug + 'static> Intern for [T] {
+    type Primitive = T;
 
-    fn as_bytes(&self) -> &[char] {
+    fn as_bytes(&self) -> &[T] {
         self
     }
 
-    unsafe fn from_bytes(bytes: &[char]) -> &Self {
+    unsafe fn from_bytes(bytes: &[T]) -> &Self {
         bytes
     }
 }
 
+impl<T: Copy + Hash + Eq + Debug + 'static> FromPrimitives for [T] {
+    fn from_bytes_checked(bytes: &[T]) -> Option<&Self> {
+        Some(bytes)
+    }
+}
+
 #[cfg(feature = "std")]
 mod std_impls {
     use super::Intern;
-    use std::ffi::OsStr;
+    use std::{ffi::OsStr, path::Path};
+
+    #[cfg(unix)]
+    impl super::FromPrimitives for OsStr {
+        fn from_bytes_checked(bytes: &[u8]) -> Option<&Self> {
+            // On Unix the encoded bytes are arbitrary bytes.
+            Some(std::os::unix::ffi::OsStrExt::from_bytes(bytes))
+        }
+    }
+
+    #[cfg(unix)]
+    impl super::FromPrimitives for Path {
+        fn from_bytes_checked(bytes: &[u8]) -> Option<&Self> {
+            OsStr::from_bytes_checked(bytes).map(Path::new)
+        }
+    }
 
     unsafe impl Intern for OsStr {
         type Primitive = u8;
@@ -88,6 +122,19 @@ mod std_impls {
             unsafe { OsStr::from_encoded_bytes_unchecked(bytes) }
         }
     }
+
+    unsafe impl Intern for Path {
+        type Primitive = u8;
+
+        fn as_bytes(&self) -> &[u8] {
+            self.as_os_str().as_encoded_bytes()
+        }
+
+        unsafe fn from_bytes(bytes: &[u8]) -> &Self {
+            // SAFETY: Calling this function is only valid with bytes obtained from `Self::as_bytes`.
+            Path::new(unsafe { OsStr::from_encoded_bytes_unchecked(bytes) })
+        }
+    }
 }
 
 // TODO: add impl for `[std::ascii::Char]` when stable.
@@ -1,15 +1,28 @@
 use crate::{
-    backend::{Iter, IterWithHashes, StringBackend},
+    backend::{Backend, HashedBackend, Iter, Offset, StringBackend},
+    entry::{Entry, VacantEntry},
     intern::Intern,
-    DefaultSymbol, Symbol,
+    symbol::expect_valid_symbol,
+    Checkpoint, DefaultSymbol, FixedHashBuilder, FrozenInterner, HashStats, InternError,
+    MemoryUsage, ReserveError, ResolveError, ResolveIndex, ResolveTable, Symbol,
+};
+use alloc::{
+    borrow::ToOwned,
+    collections::TryReserveError,
+    ffi::{CString, NulError},
+    vec::Vec,
 };
 use core::{
+    ffi::CStr,
     fmt,
     fmt::{Debug, Formatter},
-    hash::{BuildHasher, Hasher},
-    iter::FromIterator,
+    hash::{BuildHasher, Hash, Hasher},
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+    ops::Index,
+    str::Utf8Error,
 };
-use hashbrown::{DefaultHashBuilder, HashMap};
+use hashbrown::{hash_table, DefaultHashBuilder, HashTable};
 
 /// Creates the `u64` hash value for the given value using the given hash builder.
 fn make_hash<I: Intern + ?Sized>(builder: &impl BuildHasher, value: &I) -> u64 {
@@ -29,50 +42,198 @@ fn make_hash<I: Intern + ?Sized>(builder: &impl BuildHasher, value: &I) -> u64 {
 ///     - This maps from `string` type to `symbol` type.
 /// - [`Interner::resolve`]: To resolve your already interned strings.
 ///     - This maps from `symbol` type to `string` type.
-pub struct Interner<I: Intern + ?Sized, S: Symbol = DefaultSymbol, H = DefaultHashBuilder> {
-    dedup: HashMap<S, (), ()>,
+///
+/// The storage of the interned strings is provided by the [`Backend`],
+/// see the [`backend`](crate::backend) module for the available ones.
+pub struct Interner<
+    I: Intern + ?Sized,
+    S: Symbol = DefaultSymbol,
+    H = DefaultHashBuilder,
+    B: Backend<I, S> = StringBackend<I, S>,
+> {
+    dedup: HashTable<S>,
     hasher: H,
-    backend: StringBackend<I, S>,
+    backend: B,
+    marker: PhantomData<fn(&I)>,
+    /// Incremented whenever strings are removed, to detect stale checkpoints.
+    #[cfg(debug_assertions)]
+    generation: usize,
 }
 
-impl<I: Intern + ?Sized, S: Symbol, H> Debug for Interner<I, S, H>
+/// Formats the interner as a map from the index of every symbol to its string,
+/// like `{0: "Elephant", 1: "Tiger"}`.
+impl<I: Intern + ?Sized, S: Symbol, H, B: Backend<I, S>> Debug for Interner<I, S, H, B>
 where
-    S: Debug,
+    I: Debug,
     H: BuildHasher,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("StringInterner")
-            .field("dedup", &self.dedup)
-            .field("backend", &self.backend)
+        f.debug_map()
+            .entries(
+                self.iter()
+                    .map(|(symbol, string)| (symbol.to_usize(), string)),
+            )
             .finish()
     }
 }
 
-impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default> Default for Interner<I, S, H> {
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default, B: Backend<I, S>> Default
+    for Interner<I, S, H, B>
+{
     #[cfg_attr(feature = "inline-more", inline)]
     fn default() -> Self {
         Interner::new()
     }
 }
 
-impl<I: Intern + ?Sized, S: Symbol, H: Clone> Clone for Interner<I, S, H> {
+impl<I: Intern + ?Sized, S: Symbol, H: Clone, B: Backend<I, S> + Clone> Clone
+    for Interner<I, S, H, B>
+{
     fn clone(&self) -> Self {
         Self {
             dedup: self.dedup.clone(),
             hasher: self.hasher.clone(),
             backend: self.backend.clone(),
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: self.generation,
+        }
+    }
+
+    /// Reuses the allocations of `self` where possible.
+    fn clone_from(&mut self, source: &Self) {
+        self.dedup.clone_from(&source.dedup);
+        self.hasher.clone_from(&source.hasher);
+        self.backend.clone_from(&source.backend);
+        #[cfg(debug_assertions)]
+        {
+            self.generation = source.generation;
         }
     }
 }
 
-impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default> Interner<I, S, H> {
+/// Compares the interners as sets of strings, regardless of their symbols.
+///
+/// See [`Interner::eq_ordered`] to also compare the order in which the strings were interned.
+impl<I, S, H, B, H2, B2> PartialEq<Interner<I, S, H2, B2>> for Interner<I, S, H, B>
+where
+    I: Intern + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+    B: Backend<I, S>,
+    H2: BuildHasher,
+    B2: Backend<I, S>,
+{
+    fn eq(&self, other: &Interner<I, S, H2, B2>) -> bool {
+        self.len() == other.len()
+            && other.strings().all(|string| {
+                self.get_hashed(string, make_hash(&self.hasher, string))
+                    .is_some()
+            })
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher, B: Backend<I, S>> Eq for Interner<I, S, H, B> {}
+
+/// Compares the strings of the interner with the given strings in order of their symbols,
+/// like [`Interner::eq_ordered`].
+///
+/// Unlike `==` between interners, the order of the strings matters.
+///
+/// # Example
+///
+/// ```
+/// use string_hash_interner::DefaultStringInterner;
+///
+/// let interner = DefaultStringInterner::from_iter(["a", "b", "a", "c"]);
+/// assert_eq!(interner, ["a", "b", "c"]);
+/// assert_ne!(interner, ["a", "c", "b"]);
+/// assert_eq!(interner, vec![String::from("a"), String::from("b"), String::from("c")]);
+/// ```
+impl<I, S, H, B, T> PartialEq<[T]> for Interner<I, S, H, B>
+where
+    I: Intern + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+    B: Backend<I, S>,
+    T: AsRef<I>,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        self.len() == other.len()
+            && self
+                .strings()
+                .zip(other)
+                .all(|(string, other)| string == other.as_ref())
+    }
+}
+
+/// Compares the strings of the interner with the given strings in order of their symbols.
+///
+/// See the implementation for slices.
+impl<I, S, H, B, T, const N: usize> PartialEq<[T; N]> for Interner<I, S, H, B>
+where
+    I: Intern + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+    B: Backend<I, S>,
+    T: AsRef<I>,
+{
+    #[inline]
+    fn eq(&self, other: &[T; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+/// Compares the strings of the interner with the given strings in order of their symbols.
+///
+/// See the implementation for slices.
+impl<I, S, H, B, T> PartialEq<Vec<T>> for Interner<I, S, H, B>
+where
+    I: Intern + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+    B: Backend<I, S>,
+    T: AsRef<I>,
+{
+    #[inline]
+    fn eq(&self, other: &Vec<T>) -> bool {
+        *self == other[..]
+    }
+}
+
+/// Hashes the interners as sets of strings, consistently with `==`.
+///
+/// The hash doesn't depend on the order in which the strings were interned,
+/// nor on the hasher of the interner: every string is hashed with a [`FixedHashBuilder`]
+/// and the hashes are summed up. See [`Interner::hash_ordered`] for a hash
+/// which is consistent with [`Interner::eq_ordered`] instead.
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher, B: Backend<I, S>> Hash
+    for Interner<I, S, H, B>
+{
+    fn hash<T: Hasher>(&self, state: &mut T) {
+        let hasher = FixedHashBuilder::default();
+        let sum = self
+            .strings()
+            .map(|string| make_hash(&hasher, string))
+            .fold(0u64, u64::wrapping_add);
+        state.write_usize(self.len());
+        state.write_u64(sum);
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default, B: Backend<I, S>>
+    Interner<I, S, H, B>
+{
     /// Creates a new empty [`Interner`].
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn new() -> Self {
         Self {
-            dedup: HashMap::default(),
+            dedup: HashTable::new(),
             hasher: Default::default(),
-            backend: StringBackend::default(),
+            backend: B::default(),
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
         }
     }
 
@@ -80,21 +241,68 @@ impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default> Interner<I, S, H>
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn with_capacity(cap: usize) -> Self {
         Self {
-            dedup: HashMap::with_capacity_and_hasher(cap, ()),
+            dedup: HashTable::with_capacity(cap),
             hasher: Default::default(),
-            backend: StringBackend::with_capacity(cap),
+            backend: B::with_capacity(cap),
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
         }
     }
+
+    /// Creates a new empty [`Interner`] with capacity for at least `cap` strings,
+    /// and for at least `buffer_cap` elements of type [`Intern::Primitive`]
+    /// in the buffer of strings.
+    ///
+    /// Unlike [`Interner::with_capacity`], which reserves the buffer assuming
+    /// an average length of the strings, the buffer is reserved exactly as requested.
+    /// Backends may store more than the contents in the buffer, like the lengths
+    /// of the strings in the [`PrefixedBackend`](crate::backend::PrefixedBackend).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let interner = DefaultStringInterner::with_capacities(100, 6000);
+    /// assert!(interner.capacity() >= 100);
+    /// assert!(interner.buffer_capacity() >= 6000);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacities(cap: usize, buffer_cap: usize) -> Self {
+        Self::with_capacities_and_hasher(cap, buffer_cap, Default::default())
+    }
 }
 
-impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher> Interner<I, S, H> {
+impl<I: Intern + ?Sized, S: Symbol, B: Backend<I, S>> Interner<I, S, FixedHashBuilder, B> {
+    /// Creates a new empty [`Interner`] with a [`FixedHashBuilder`] seeded with `seed`.
+    ///
+    /// Interners created with the same `seed` produce the same hashes for the same strings,
+    /// even across different runs of the program, which is useful for reproducible hashes,
+    /// like in snapshot tests.
+    ///
+    /// # Note
+    ///
+    /// Unlike the randomly seeded [`DefaultHashBuilder`], a fixed seed makes the hashes predictable,
+    /// so the interner is not resistant to HashDoS attacks. Don't use a fixed seed when
+    /// interning strings from untrusted sources.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_hasher(FixedHashBuilder::with_seed(seed))
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher, B: Backend<I, S>> Interner<I, S, H, B> {
     /// Creates a new empty `StringInterner` with the given hasher.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn with_hasher(hash_builder: H) -> Self {
         Interner {
-            dedup: HashMap::default(),
+            dedup: HashTable::new(),
             hasher: hash_builder,
-            backend: StringBackend::default(),
+            backend: B::default(),
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
         }
     }
 
@@ -102,12 +310,41 @@ impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher> Interner<I, S, H> {
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn with_capacity_and_hasher(cap: usize, hash_builder: H) -> Self {
         Interner {
-            dedup: HashMap::with_capacity_and_hasher(cap, ()),
+            dedup: HashTable::with_capacity(cap),
             hasher: hash_builder,
-            backend: StringBackend::with_capacity(cap),
+            backend: B::with_capacity(cap),
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
         }
     }
 
+    /// Creates a new empty [`Interner`] with the given capacities and the given hasher.
+    ///
+    /// See [`Interner::with_capacities`].
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn with_capacities_and_hasher(cap: usize, buffer_cap: usize, hash_builder: H) -> Self {
+        Interner {
+            dedup: HashTable::with_capacity(cap),
+            hasher: hash_builder,
+            backend: B::with_capacities(cap, buffer_cap),
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Returns a reference to the hasher of the interner.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
     /// Returns the number of strings interned by the interner.
     #[cfg_attr(feature = "inline-more", inline)]
     pub fn len(&self) -> usize {
@@ -120,9 +357,56 @@ impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher> Interner<I, S, H> {
         self.len() == 0
     }
 
+    /// Returns the symbol that the next newly interned string will get.
+    ///
+    /// The prediction holds only if the next interned string is not interned yet,
+    /// otherwise [`Interner::intern`] returns the symbol of the already interned string.
+    ///
+    /// Returns `None` if the chosen symbol type can't represent any more strings.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn next_symbol(&self) -> Option<S> {
+        S::try_from_usize(self.len())
+    }
+
+    /// Returns the symbol of the first interned string, or `None` if the interner is empty.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn first_symbol(&self) -> Option<S> {
+        if self.is_empty() {
+            return None;
+        }
+        S::try_from_usize(0)
+    }
+
+    /// Returns the symbol of the most recently interned string,
+    /// or `None` if the interner is empty.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn last_symbol(&self) -> Option<S> {
+        S::try_from_usize(self.len().checked_sub(1)?)
+    }
+
     /// Returns the symbol for the given string if any.
     ///
     /// Can be used to query if a string has already been interned without interning.
+    ///
+    /// Like all methods that take strings, this accepts anything that implements
+    /// [`AsRef<I>`], like literals, [`String`](alloc::string::String)s, `Box<str>`s
+    /// and `Cow<str>`s, or [`Vec<u8>`]s and byte arrays for `[u8]` interners,
+    /// as well as references to them. [`Borrow<I>`](core::borrow::Borrow) is not used,
+    /// because references like `&String` or `&[u8; 4]` don't implement it.
+    ///
+    /// The string is hashed on every call. To look up the same strings repeatedly,
+    /// compute their hashes once with [`Interner::hash_of`] and use [`Interner::get_with_hash`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::Interner;
+    ///
+    /// let mut interner = <Interner<[u8]>>::new();
+    /// let symbol = interner.intern(b"abcd");
+    /// assert_eq!(interner.get(&[b'a', b'b', b'c', b'd']), Some(symbol));
+    /// assert_eq!(interner.get(vec![b'a', b'b', b'c', b'd']), Some(symbol));
+    /// ```
     #[inline]
     pub fn get<T>(&self, string: T) -> Option<S>
     where
@@ -131,132 +415,1951 @@ impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher> Interner<I, S, H> {
         let string = string.as_ref();
 
         let hash = make_hash(&self.hasher, string);
-        self.dedup
-            .raw_entry()
-            .from_hash(hash, |symbol| {
-                // SAFETY: This is safe because we only operate on symbols that
-                //         we receive from our backend making them valid.
-                string == unsafe { self.backend.resolve_unchecked(*symbol) }
-            })
-            .map(|(&symbol, &())| symbol)
+        self.get_hashed(string, hash)
     }
 
-    /// Interns the given string.
+    /// Returns `true` if the given string has already been interned.
+    #[inline]
+    pub fn contains<T>(&self, string: T) -> bool
+    where
+        T: AsRef<I>,
+    {
+        let string = string.as_ref();
+        let hash = make_hash(&self.hasher, string);
+        self.contains_hashed(string, hash)
+    }
+
+    /// Returns `true` if the given `symbol` resolves to a string of the interner.
     ///
-    /// Returns a symbol for resolution into the original string, and its hash.
+    /// This only compares the index of the `symbol` with [`Interner::len`],
+    /// so it is cheaper than checking [`Interner::resolve`]. If it returns `true`,
+    /// calling [`Interner::resolve_unchecked`] with the `symbol` is sound
+    /// as long as the interner is not truncated or cleared.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn contains_symbol(&self, symbol: S) -> bool {
+        symbol.to_usize() < self.len()
+    }
+
+    /// Returns the symbol for the given string.
+    ///
+    /// This is the panicking version of [`Interner::get`], for strings
+    /// that are known to be interned.
     ///
     /// # Panics
     ///
-    /// If the interner already interns the maximum number of strings possible
-    /// by the chosen symbol type.
+    /// If the string is not interned.
     #[inline]
-    pub fn intern_and_hash<T: AsRef<I>>(&mut self, string: T) -> (S, u64) {
-        let string = string.as_ref();
+    #[track_caller]
+    pub fn symbol<T>(&self, string: T) -> S
+    where
+        T: AsRef<I>,
+    {
+        self.get(string).expect("string is not interned")
+    }
 
-        let hash = make_hash(&self.hasher, string);
-        let entry = self.dedup.raw_entry_mut().from_hash(hash, |symbol| {
+    /// Returns the hash of the given string, as computed by the interner.
+    ///
+    /// The string doesn't have to be interned. The hash equals the one cached by
+    /// backends which implement [`HashedBackend`] once the string is interned,
+    /// and can be passed to [`Interner::get_with_hash`] and [`Interner::intern_with_hash`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let hash = interner.hash_of("hello");
+    /// let hello = interner.intern_with_hash("hello", hash);
+    /// assert_eq!(interner.get_hash(hello), Some(hash));
+    /// ```
+    #[inline]
+    pub fn hash_of<T: AsRef<I>>(&self, string: T) -> u64 {
+        make_hash(&self.hasher, string.as_ref())
+    }
+
+    /// Returns the symbol for the given string if any, using the provided `hash`
+    /// instead of computing it.
+    ///
+    /// This is useful for translating symbols between interners that share
+    /// the same [`BuildHasher`], as the cached hash from one interner
+    /// can be used for the look-up in the other one, or when the hash was already
+    /// computed with the same [`BuildHasher`] elsewhere.
+    ///
+    /// The `hash` must be produced by the same hasher as the one used by `self`.
+    /// Otherwise the look-up will most likely return `None`, even if the string is interned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let keys = ["fn", "let", "mut"];
+    /// let hashes = keys.map(|key| interner.hash_of(key));
+    ///
+    /// for string in ["mut", "let"] {
+    ///     interner.intern(string);
+    ///     let found = keys
+    ///         .iter()
+    ///         .zip(hashes)
+    ///         .filter_map(|(key, hash)| interner.get_with_hash(key, hash));
+    ///     assert_eq!(found.count(), interner.len());
+    /// }
+    /// ```
+    #[inline]
+    pub fn get_with_hash<T>(&self, string: T, hash: u64) -> Option<S>
+    where
+        T: AsRef<I>,
+    {
+        self.get_hashed(string.as_ref(), hash)
+    }
+
+    #[inline]
+    pub(crate) fn get_hashed(&self, string: &I, hash: u64) -> Option<S> {
+        self.dedup.find(hash, self.resolves_to(string)).copied()
+    }
+
+    #[inline]
+    pub(crate) fn contains_hashed(&self, string: &I, hash: u64) -> bool {
+        self.dedup.find(hash, self.resolves_to(string)).is_some()
+    }
+
+    /// Returns a closure checking whether a symbol of the deduplication map
+    /// resolves to `string`.
+    #[inline]
+    fn resolves_to<'a>(&'a self, string: &'a I) -> impl Fn(&S) -> bool + 'a {
+        move |symbol| {
             // SAFETY: This is safe because we only operate on symbols that
             //         we receive from our backend making them valid.
             string == unsafe { self.backend.resolve_unchecked(*symbol) }
-        });
-        use hashbrown::hash_map::RawEntryMut;
-        let (&mut symbol, &mut ()) = match entry {
-            RawEntryMut::Occupied(occupied) => occupied.into_key_value(),
-            RawEntryMut::Vacant(vacant) => {
-                let symbol = self.backend.intern(string, hash);
-                vacant.insert_with_hasher(hash, symbol, (), |symbol| {
-                    // SAFETY: This is safe because we only operate on symbols that
-                    //         we receive from our backend making them valid.
-                    unsafe { self.backend.get_hash_unchecked(*symbol) }
-                })
-            }
-        };
-        (symbol, hash)
+        }
     }
 
     /// Interns the given string.
     ///
-    /// Returns a symbol for resolution into the original string.
+    /// Returns a symbol for resolution into the original string, and its hash.
     ///
-    /// # Panics
+    /// # Errors
     ///
     /// If the interner already interns the maximum number of strings possible
-    /// by the chosen symbol type.
+    /// by the chosen symbol type. The interner is left unchanged in this case.
     #[inline]
-    pub fn intern<T: AsRef<I>>(&mut self, string: T) -> S {
-        self.intern_and_hash(string).0
+    pub fn try_intern_and_hash<T: AsRef<I>>(&mut self, string: T) -> Result<(S, u64), InternError> {
+        let string = string.as_ref();
+
+        let hash = make_hash(&self.hasher, string);
+        let symbol = self.try_intern_hashed(string, hash)?;
+        Ok((symbol, hash))
     }
 
-    /// Shrink backend capacity to fit the interned strings exactly.
-    pub fn shrink_to_fit(&mut self) {
-        self.backend.shrink_to_fit()
+    /// Returns the [`Entry`] of the given string, to intern it only if needed.
+    ///
+    /// The string is hashed and looked up only once, even if it gets interned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use string_hash_interner::{DefaultStringInterner, Entry};
+    /// let mut interner = DefaultStringInterner::new();
+    /// let symbol = match interner.entry("Earth") {
+    ///     Entry::Occupied(symbol) => symbol,
+    ///     Entry::Vacant(vacant) => vacant.intern(),
+    /// };
+    /// assert_eq!(interner.entry("Earth").symbol(), Some(symbol));
+    /// ```
+    #[inline]
+    pub fn entry<'a, T>(&'a mut self, string: &'a T) -> Entry<'a, I, S, B>
+    where
+        T: AsRef<I> + ?Sized,
+    {
+        let string = string.as_ref();
+        let hash = make_hash(&self.hasher, string);
+        self.entry_hashed(string, hash)
     }
 
-    /// Returns the string for the given `symbol`` if any.
     #[inline]
-    pub fn resolve(&self, symbol: S) -> Option<&I> {
-        self.backend.resolve(symbol)
+    pub(crate) fn entry_hashed<'a>(&'a mut self, string: &'a I, hash: u64) -> Entry<'a, I, S, B> {
+        let entry = self.dedup.entry(
+            hash,
+            |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                string == unsafe { self.backend.resolve_unchecked(*symbol) }
+            },
+            |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe {
+                    self.backend
+                        .hash_unchecked(*symbol, |string| make_hash(&self.hasher, string))
+                }
+            },
+        );
+        match entry {
+            hash_table::Entry::Occupied(occupied) => Entry::Occupied(*occupied.get()),
+            hash_table::Entry::Vacant(vacant) => Entry::Vacant(VacantEntry {
+                entry: vacant,
+                backend: &mut self.backend,
+                string,
+                hash,
+            }),
+        }
     }
 
-    /// Returns cached hash of the string for the given `symbol`.
-    pub fn get_hash(&self, symbol: S) -> Option<u64> {
-        self.backend.get_hash(symbol)
+    #[inline]
+    pub(crate) fn try_intern_hashed(&mut self, string: &I, hash: u64) -> Result<S, InternError> {
+        match self.entry_hashed(string, hash) {
+            Entry::Occupied(symbol) => Ok(symbol),
+            Entry::Vacant(vacant) => vacant.try_intern(),
+        }
     }
 
-    /// Returns the string for the given `symbol` without performing any checks.
+    /// Interns the given string.
     ///
-    /// # Safety
+    /// Returns a symbol for resolution into the original string.
     ///
-    /// It is the caller's responsibility to provide this method with `symbol`s
-    /// that are valid for the [`Interner`].
+    /// # Errors
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type. The interner is left unchanged in this case.
     #[inline]
-    pub unsafe fn resolve_unchecked(&self, symbol: S) -> &I {
-        unsafe { self.backend.resolve_unchecked(symbol) }
+    pub fn try_intern<T: AsRef<I>>(&mut self, string: T) -> Result<S, InternError> {
+        self.try_intern_and_hash(string).map(|(symbol, _)| symbol)
     }
 
-    /// Returns cached hash of the string for the given `symbol` without performing any checks.
+    /// Interns the given string.
     ///
-    /// # Safety
+    /// Returns a symbol for resolution into the original string, and its hash.
     ///
-    /// It is the caller's responsibility to provide this method with `symbol`s
-    /// that are valid for the [`Interner`].
-    pub unsafe fn get_hash_unchecked(&self, symbol: S) -> u64 {
-        // SAFETY: The function is marked unsafe so that the caller guarantees
-        //         that required invariants are checked.
-        unsafe { self.backend.get_hash_unchecked(symbol) }
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn intern_and_hash<T: AsRef<I>>(&mut self, string: T) -> (S, u64) {
+        self.try_intern_and_hash(string)
+            .expect("encountered invalid symbol")
     }
 
-    /// Returns an iterator that yields all interned strings, their symbols, and hashes.
+    /// Interns the given string.
+    ///
+    /// Returns a symbol for resolution into the original string, and the interned string,
+    /// without looking it up again like a following [`Interner::resolve`] would.
+    ///
+    /// The returned string keeps the interner borrowed mutably, since Rust can't turn
+    /// the mutable borrow of `self` into a shared one. So the interner can't be used
+    /// while the string is alive, only the symbol outlives it.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let (symbol, string) = interner.intern_and_resolve(String::from("hello"));
+    /// assert_eq!(string, "hello");
+    /// assert_eq!(interner.resolve(symbol), Some("hello"));
+    /// ```
     #[inline]
-    pub fn iter_with_hashes(&self) -> IterWithHashes<'_, I, S> {
-        self.backend.iter_with_hashes()
+    pub fn intern_and_resolve<T: AsRef<I>>(&mut self, string: T) -> (S, &I) {
+        let symbol = self.intern(string);
+        // SAFETY: The symbol was just returned by the interner, so it is valid.
+        (symbol, unsafe { self.backend.resolve_unchecked(symbol) })
     }
 
-    /// Returns an iterator that yields all interned strings and their symbols.
+    /// Interns the given string, using the provided `hash` instead of computing it.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// The `hash` must be produced by the same hasher as the one used by `self`.
+    /// Passing another hash is not undefined behavior, but the string will most likely
+    /// be interned again under a new symbol, and look-ups of it with [`Interner::get`]
+    /// will miss.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
     #[inline]
-    pub fn iter(&self) -> Iter<'_, I, S> {
-        self.backend.iter()
+    pub fn intern_with_hash<T: AsRef<I>>(&mut self, string: T, hash: u64) -> S {
+        self.try_intern_hashed(string.as_ref(), hash)
+            .expect("encountered invalid symbol")
     }
-}
 
-impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default, T: AsRef<I>> FromIterator<T>
-    for Interner<I, S, H>
-{
-    fn from_iter<It>(iter: It) -> Self
-    where
-        It: IntoIterator<Item = T>,
-    {
-        let iter = iter.into_iter();
-        let (capacity, _) = iter.size_hint();
-        let mut interner = Self::with_capacity(capacity);
-        interner.extend(iter);
-        interner
+    /// Interns the given string.
+    ///
+    /// Returns a symbol for resolution into the original string, its hash,
+    /// and `true` if the string was not interned before.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn intern_full<T: AsRef<I>>(&mut self, string: T) -> (S, u64, bool) {
+        let string = string.as_ref();
+        let hash = make_hash(&self.hasher, string);
+        match self.entry_hashed(string, hash) {
+            Entry::Occupied(symbol) => (symbol, hash, false),
+            Entry::Vacant(vacant) => (vacant.intern(), hash, true),
+        }
     }
-}
 
-impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default, T: AsRef<I>> Extend<T>
-    for Interner<I, S, H>
+    /// Interns the given string.
+    ///
+    /// Returns a symbol for resolution into the original string.
+    ///
+    /// The empty string is interned like any other string: it gets the next symbol
+    /// the first time, takes no space in the buffer, and resolves to an empty string.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn intern<T: AsRef<I>>(&mut self, string: T) -> S {
+        self.intern_and_hash(string).0
+    }
+
+    /// Same as [`Interner::intern`].
+    ///
+    /// For compatibility with the [`string-interner`](https://docs.rs/string-interner) crate.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern<T: AsRef<I>>(&mut self, string: T) -> S {
+        self.intern(string)
+    }
+
+    /// Same as [`Interner::intern_static`].
+    ///
+    /// For compatibility with the [`string-interner`](https://docs.rs/string-interner) crate.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn get_or_intern_static(&mut self, string: &'static I) -> S {
+        self.intern_static(string)
+    }
+
+    /// Interns the given `'static` string.
+    ///
+    /// Backends may store a reference to the string instead of copying it,
+    /// see [`Backend::try_intern_static`]. Only the [`BucketBackend`](crate::backend::BucketBackend)
+    /// does so, since the other backends keep all strings in one contiguous buffer,
+    /// which [`Interner::as_parts`] and the [`ResolveTable`] rely on.
+    ///
+    /// # Errors
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type. The interner is left unchanged in this case.
+    #[inline]
+    pub fn try_intern_static(&mut self, string: &'static I) -> Result<S, InternError> {
+        let hash = make_hash(&self.hasher, string);
+        match self.entry_hashed(string, hash) {
+            Entry::Occupied(symbol) => Ok(symbol),
+            Entry::Vacant(vacant) => vacant.try_intern_static(string),
+        }
+    }
+
+    /// Interns the given `'static` string.
+    ///
+    /// See [`Interner::try_intern_static`] for the non-panicking version.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    #[inline]
+    pub fn intern_static(&mut self, string: &'static I) -> S {
+        self.try_intern_static(string)
+            .expect("encountered invalid symbol")
+    }
+
+    /// Returns the number of strings the interner can hold without reallocating.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn capacity(&self) -> usize {
+        self.dedup.capacity().min(self.backend.capacity())
+    }
+
+    /// Returns the number of strings the deduplication map can hold without growing.
+    ///
+    /// The map allocates a power of two number of buckets, and grows once 7/8 of them
+    /// are full, or all of them below 8 buckets. The capacity is that threshold,
+    /// so interning more strings than `dedup_capacity() - len()` doubles the map
+    /// and reinserts every symbol, with the cached hash or by hashing the string again.
+    /// [`Interner::capacity`] is the smaller of this and the capacity of the backend.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::with_capacity(100);
+    /// assert!(interner.dedup_capacity() >= 100);
+    /// let spare = interner.dedup_capacity() - interner.len();
+    /// interner.extend((0..spare).map(|n| n.to_string()));
+    /// assert_eq!(interner.dedup_capacity(), interner.len());
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn dedup_capacity(&self) -> usize {
+        self.dedup.capacity()
+    }
+
+    /// Returns the number of elements of string contents the interner can hold
+    /// without reallocating.
+    ///
+    /// See [`Interner::reserve_buffer`] for what is counted.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn buffer_capacity(&self) -> usize {
+        self.backend.buffer_capacity()
+    }
+
+    /// Returns the number of elements of string contents held by the interner.
+    ///
+    /// Counted like [`Interner::buffer_capacity`], so the length prefixes
+    /// of the [`PrefixedBackend`](crate::backend::PrefixedBackend) are included,
+    /// and strings interned by [`Interner::intern_static`] with the
+    /// [`BucketBackend`](crate::backend::BucketBackend) are not.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let interner = DefaultStringInterner::from_iter(["hello", "world", "!"]);
+    /// assert_eq!(interner.buffer_len(), 11);
+    /// assert_eq!(interner.buffer_len() / interner.len(), 3);
+    /// ```
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn buffer_len(&self) -> usize {
+        self.backend.buffer_len()
+    }
+
+    /// Returns the number of bytes allocated by the interner.
+    ///
+    /// The counts of the buffer and of the index of strings come from
+    /// [`Backend::memory_usage`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::with_capacity(16);
+    /// interner.intern("hello");
+    /// let usage = interner.memory_usage();
+    /// assert!(usage.buffer_bytes > 0 && usage.dedup_bytes > 0);
+    /// assert_eq!(
+    ///     usage.total(),
+    ///     usage.buffer_bytes + usage.ends_bytes + usage.dedup_bytes,
+    /// );
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            dedup_bytes: self.dedup.allocation_size(),
+            ..self.backend.memory_usage()
+        }
+    }
+
+    /// Returns statistics about the hashes of the interned strings,
+    /// to compare how well hashers spread the strings.
+    ///
+    /// The hashes cached by the backend are used, or computed otherwise.
+    /// This allocates and takes `O(n log n)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let interner = DefaultStringInterner::from_iter(["a", "b", "c"]);
+    /// let stats = interner.hash_stats();
+    /// assert_eq!(stats.distinct_hashes, 3);
+    /// assert_eq!(stats.collisions, 0);
+    /// assert_eq!(stats.max_chain_len, 1);
+    /// ```
+    pub fn hash_stats(&self) -> HashStats {
+        let mut hashes: Vec<u64> = self
+            .backend
+            .iter()
+            .map(|(symbol, string)| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe {
+                    self.backend
+                        .hash_unchecked(symbol, |_| make_hash(&self.hasher, string))
+                }
+            })
+            .collect();
+        HashStats::from_hashes(&mut hashes)
+    }
+
+    /// Returns `true` if both interners have the same strings with the same symbols.
+    ///
+    /// Unlike `==`, which compares the interners as sets of strings,
+    /// this also takes the order in which the strings were interned into account.
+    pub fn eq_ordered<H2: BuildHasher, B2: Backend<I, S>>(
+        &self,
+        other: &Interner<I, S, H2, B2>,
+    ) -> bool {
+        self.len() == other.len() && self.strings().eq(other.strings())
+    }
+
+    /// Feeds the number of strings and every string in the order of their symbols
+    /// into the given `state`.
+    ///
+    /// Unlike the [`Hash`] implementation, which is consistent with `==`,
+    /// this is consistent with [`Interner::eq_ordered`]: interners with the same strings
+    /// interned in a different order usually hash differently.
+    pub fn hash_ordered<T: Hasher>(&self, state: &mut T) {
+        state.write_usize(self.len());
+        for string in self.strings() {
+            string.hash(state);
+        }
+    }
+
+    /// Interns all given strings.
+    ///
+    /// Returns the symbols of the strings in the same order, including duplicates.
+    /// This is the [`Extend`] implementation, but keeping the symbols.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let symbols = interner.intern_all(["a", "b", "a"]);
+    /// assert_eq!(symbols[0], symbols[2]);
+    /// assert_eq!(interner.resolve(symbols[1]), Some("b"));
+    /// ```
+    pub fn intern_all<T, It>(&mut self, strings: It) -> Vec<S>
+    where
+        T: AsRef<I>,
+        It: IntoIterator<Item = T>,
+    {
+        let strings = strings.into_iter();
+        let (additional, _) = strings.size_hint();
+        self.reserve(additional);
+        strings.map(|string| self.intern(string)).collect()
+    }
+
+    /// Interns all strings of `other` into `self`.
+    ///
+    /// Returns a table mapping every symbol of `other`, indexed by [`Symbol::to_usize`],
+    /// to the symbol of the same string in `self`.
+    ///
+    /// The hashes cached by `other` are reused instead of hashing the strings again,
+    /// so `other` must use a hasher that produces the same hashes as the one of `self`,
+    /// like a clone of it or a [`FixedHashBuilder`] with the same seed.
+    /// Otherwise look-ups of the merged strings will most likely miss,
+    /// and they may be interned again.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    pub fn merge<B2: Backend<I, S>>(&mut self, other: &Interner<I, S, H, B2>) -> Vec<S> {
+        self.reserve(other.len());
+        other
+            .iter()
+            .map(|(symbol, string)| self.intern_from(other, symbol, string))
+            .collect()
+    }
+
+    /// Interns all strings of `other` into `self`.
+    ///
+    /// Like [`Interner::merge`], but doesn't build a table of the new symbols,
+    /// for when only the strings matter. The hashes cached by `other` are reused
+    /// in the same way, so the hashers must produce the same hashes.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::from_iter(["a", "b"]);
+    /// let mut scratch = DefaultStringInterner::with_hasher(*interner.hasher());
+    /// scratch.intern("b");
+    /// scratch.intern("c");
+    ///
+    /// interner.extend_from_interner(&scratch);
+    /// assert_eq!(interner.len(), 3);
+    /// assert!(interner.contains("c"));
+    /// ```
+    pub fn extend_from_interner<B2: Backend<I, S>>(&mut self, other: &Interner<I, S, H, B2>) {
+        self.reserve(other.len());
+        for (symbol, string) in other {
+            self.intern_from(other, symbol, string);
+        }
+    }
+
+    /// Interns the string of `other` with the given `symbol`, reusing its cached hash.
+    fn intern_from<B2: Backend<I, S>>(
+        &mut self,
+        other: &Interner<I, S, H, B2>,
+        symbol: S,
+        string: &I,
+    ) -> S {
+        // SAFETY: This is safe because we only operate on symbols that
+        //         we receive from the backend of `other` making them valid.
+        let hash = unsafe {
+            other
+                .backend
+                .hash_unchecked(symbol, |string| make_hash(&self.hasher, string))
+        };
+        self.try_intern_hashed(string, hash)
+            .expect("encountered invalid symbol")
+    }
+
+    /// Reserves capacity for at least `additional` more strings to be interned.
+    ///
+    /// See [`Interner::reserve_buffer`] to reserve space for the contents of the strings.
+    pub fn reserve(&mut self, additional: usize) {
+        self.dedup.reserve(additional, |symbol| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            unsafe {
+                self.backend
+                    .hash_unchecked(*symbol, |string| make_hash(&self.hasher, string))
+            }
+        });
+        self.backend.reserve(additional);
+    }
+
+    /// Reserves space for at least `additional` more elements of string contents.
+    ///
+    /// # Note
+    ///
+    /// `additional` is a count of [`Intern::Primitive`] elements, not of bytes.
+    /// For example, for `[char]` every element is 4 bytes wide.
+    /// Backends may also store some metadata in the same buffer,
+    /// like the lengths of the strings in [`PrefixedBackend`](crate::backend::PrefixedBackend).
+    pub fn reserve_buffer(&mut self, additional: usize) {
+        self.backend.reserve_buffer(additional);
+    }
+
+    /// Tries to reserve capacity for at least `additional` more strings to be interned.
+    ///
+    /// Same as [`Interner::reserve`], but returns an error instead of aborting
+    /// if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or if the allocator reports a failure.
+    /// [`ReserveError`] tells whether the deduplication map or the backend failed.
+    /// Capacity reserved before the failure is kept.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        self.dedup
+            .try_reserve(additional, |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe {
+                    self.backend
+                        .hash_unchecked(*symbol, |string| make_hash(&self.hasher, string))
+                }
+            })
+            .map_err(ReserveError::Dedup)?;
+        self.backend
+            .try_reserve(additional)
+            .map_err(ReserveError::Backend)
+    }
+
+    /// Tries to reserve space for at least `additional` more elements of string contents.
+    ///
+    /// Same as [`Interner::reserve_buffer`], but returns an error instead of aborting
+    /// if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or if the allocator reports a failure.
+    pub fn try_reserve_buffer(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.backend.try_reserve_buffer(additional)
+    }
+
+    /// Shrink capacity of the deduplication map and the backend
+    /// to fit the interned strings exactly.
+    pub fn shrink_to_fit(&mut self) {
+        self.dedup.shrink_to_fit(|symbol| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            unsafe {
+                self.backend
+                    .hash_unchecked(*symbol, |string| make_hash(&self.hasher, string))
+            }
+        });
+        self.backend.shrink_to_fit()
+    }
+
+    /// Shrinks the capacity of the deduplication map and the backend, keeping room
+    /// for at least `min_entries` strings and `min_buffer` elements of string contents.
+    ///
+    /// Like [`Vec::shrink_to`], the capacity is never shrunk below what the interned
+    /// strings need, and stays unchanged if it is already below the bounds.
+    /// Keeping some headroom avoids reallocating as soon as more strings are interned.
+    ///
+    /// # Note
+    ///
+    /// `min_entries` is a count of strings, and `min_buffer` is a count of
+    /// [`Intern::Primitive`] elements, not of bytes, like in [`Interner::reserve_buffer`].
+    /// The [`BucketBackend`](crate::backend::BucketBackend) never shrinks its buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::with_capacities(1000, 10_000);
+    /// interner.intern("hello");
+    /// interner.shrink_to(100, 500);
+    /// assert!((100..1000).contains(&interner.capacity()));
+    /// assert!((500..10_000).contains(&interner.buffer_capacity()));
+    /// ```
+    pub fn shrink_to(&mut self, min_entries: usize, min_buffer: usize) {
+        self.dedup.shrink_to(min_entries, |symbol| {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            unsafe {
+                self.backend
+                    .hash_unchecked(*symbol, |string| make_hash(&self.hasher, string))
+            }
+        });
+        self.backend.shrink_to(min_entries, min_buffer)
+    }
+
+    /// Removes all interned strings, keeping the allocated memory for reuse.
+    ///
+    /// Symbols obtained before the call are invalidated,
+    /// and the next interned string gets the first symbol again.
+    pub fn clear(&mut self) {
+        self.dedup.clear();
+        self.backend.clear();
+        #[cfg(debug_assertions)]
+        {
+            self.generation = self.generation.wrapping_add(1);
+        }
+    }
+
+    /// Removes all interned strings, returning them and their symbols as an iterator
+    /// of owned strings, and keeps the allocated memory for reuse.
+    ///
+    /// The interner is cleared when the iterator is dropped, even if it is not consumed.
+    /// If the iterator is leaked, for example with [`core::mem::forget`],
+    /// the interner keeps all its strings.
+    /// Symbols obtained before the call are invalidated, like with [`Interner::clear`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::from_iter(["a", "b"]);
+    /// let capacity = interner.capacity();
+    /// let drained: Vec<(_, String)> = interner.drain().collect();
+    /// assert_eq!(drained.len(), 2);
+    /// assert_eq!(drained[1].1, "b");
+    /// assert!(interner.is_empty());
+    /// assert_eq!(interner.capacity(), capacity);
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, I, S, H, B>
+    where
+        I: ToOwned,
+    {
+        Drain {
+            len: self.len(),
+            index: 0,
+            interner: self,
+        }
+    }
+
+    /// Removes all strings with a symbol index of `len` or greater,
+    /// keeping the allocated memory for reuse.
+    ///
+    /// Since symbols are handed out sequentially, this rolls the interner back
+    /// to the state it had when it contained `len` strings. Symbols of the removed strings
+    /// are invalidated, and interning one of them again gives it a new symbol.
+    /// Does nothing if the interner contains `len` or fewer strings.
+    ///
+    /// # Note
+    ///
+    /// Every removed string is looked up and removed from the deduplication map,
+    /// which takes `O(n)` time in the number of removed strings. If more than half
+    /// of the strings are removed, the map is scanned once instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// interner.intern("fn");
+    /// let checkpoint = interner.len();
+    /// interner.intern("speculative");
+    /// interner.truncate(checkpoint);
+    /// assert_eq!(interner.get("speculative"), None);
+    /// assert_eq!(interner.len(), 1);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        self.truncate_strings(len);
+        #[cfg(debug_assertions)]
+        {
+            self.generation = self.generation.wrapping_add(1);
+        }
+    }
+
+    /// Removes all strings with a symbol index of `len` or greater,
+    /// without invalidating checkpoints.
+    fn truncate_strings(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+        if old_len - len > len {
+            self.dedup.retain(|symbol| symbol.to_usize() < len);
+        } else {
+            for index in len..old_len {
+                let symbol = expect_valid_symbol(index);
+                // SAFETY: This is safe because the index is less than the number of strings.
+                let hash = unsafe {
+                    self.backend
+                        .hash_unchecked(symbol, |string| make_hash(&self.hasher, string))
+                };
+                if let Ok(entry) = self.dedup.find_entry(hash, |&other| other == symbol) {
+                    entry.remove();
+                }
+            }
+        }
+        self.backend.truncate(len);
+    }
+
+    /// Keeps only the strings for which `f` returns `true`, and removes all others.
+    ///
+    /// Returns a table mapping every former symbol, indexed by [`Symbol::to_usize`],
+    /// to the new symbol of its string, or to `None` if the string was removed.
+    ///
+    /// # Symbol stability
+    ///
+    /// Symbols are indices into the backend, so the kept strings are moved together
+    /// to close the gaps, keeping their relative order. Every symbol obtained before the call
+    /// must be translated with the returned table, even the ones of kept strings:
+    /// a former symbol may now resolve to a different string.
+    ///
+    /// # Note
+    ///
+    /// This is an `O(n)` operation, which moves the kept strings together with
+    /// [`Backend::retain`] and rebuilds the deduplication map. The cached hashes are reused.
+    /// Interners which accumulate garbage can call it periodically with the set of live symbols,
+    /// instead of counting the references to every symbol.
+    /// If `f` panics, the interner is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::{DefaultStringInterner, Symbol};
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let a = interner.intern("a");
+    /// let tmp = interner.intern("tmp");
+    /// let b = interner.intern("b");
+    ///
+    /// let remap = interner.retain(|_, string| string != "tmp");
+    /// assert_eq!(remap[tmp.to_usize()], None);
+    /// let b = remap[b.to_usize()].unwrap();
+    /// assert_eq!(interner.resolve(b), Some("b"));
+    /// assert_eq!(remap[a.to_usize()], Some(a));
+    /// assert_eq!(interner.len(), 2);
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(S, &I) -> bool) -> Vec<Option<S>> {
+        let mut remap = Vec::with_capacity(self.len());
+        let mut len = 0;
+        self.backend.retain(
+            |symbol, string| {
+                let keep = f(symbol, string);
+                remap.push(keep.then(|| {
+                    len += 1;
+                    expect_valid_symbol(len - 1)
+                }));
+                keep
+            },
+            |string| make_hash(&self.hasher, string),
+        );
+        self.rebuild_dedup();
+        #[cfg(debug_assertions)]
+        {
+            self.generation = self.generation.wrapping_add(1);
+        }
+        remap
+    }
+
+    /// Returns a checkpoint of the current state, which the interner can be rolled back to
+    /// with [`Interner::rollback`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let x = interner.intern("x");
+    /// let checkpoint = interner.checkpoint();
+    /// interner.intern("y");
+    /// interner.intern("z");
+    /// interner.rollback(checkpoint);
+    /// assert!(interner.iter().eq([(x, "x")]));
+    /// ```
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint::new(
+            self.len(),
+            #[cfg(debug_assertions)]
+            self.generation,
+        )
+    }
+
+    /// Removes all strings interned after the `checkpoint` was taken,
+    /// keeping the allocated memory for reuse.
+    ///
+    /// Same as [`Interner::truncate`] with [`Checkpoint::count`]. The `checkpoint`
+    /// must have been taken from this interner, and no strings may have been removed
+    /// since, for example with [`Interner::clear`]. This is checked in debug builds
+    /// as far as possible.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        debug_assert!(
+            checkpoint.count() <= self.len(),
+            "checkpoint used after strings were removed from the interner"
+        );
+        #[cfg(debug_assertions)]
+        assert_eq!(
+            checkpoint.generation(),
+            self.generation,
+            "checkpoint used after strings were removed from the interner"
+        );
+        self.truncate_strings(checkpoint.count());
+    }
+
+    /// Removes the most recently interned string and returns its former symbol.
+    ///
+    /// Returns `None` if the interner is empty. See [`Interner::truncate`].
+    pub fn pop(&mut self) -> Option<S> {
+        let len = self.len().checked_sub(1)?;
+        self.truncate(len);
+        Some(expect_valid_symbol(len))
+    }
+
+    /// Returns the string for the given `symbol`` if any.
+    ///
+    /// See the [`Index`] implementation for the panicking version.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&I> {
+        self.backend.resolve(symbol)
+    }
+
+    /// Returns the string for the given `symbol`.
+    ///
+    /// Same as [`Interner::resolve`], but the error tells the index of the `symbol`
+    /// and the number of interned strings, which helps to find symbols
+    /// that were used with the wrong interner.
+    ///
+    /// # Errors
+    ///
+    /// [`ResolveError::OutOfRange`] if the `symbol` is not valid for the interner.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::{DefaultStringInterner, ResolveError};
+    ///
+    /// let mut large = DefaultStringInterner::from_iter(["a", "b", "c"]);
+    /// let c = large.intern("c");
+    /// let small = DefaultStringInterner::from_iter(["a"]);
+    /// assert_eq!(large.try_resolve(c), Ok("c"));
+    /// assert_eq!(small.try_resolve(c), Err(ResolveError::OutOfRange { index: 2, len: 1 }));
+    /// ```
+    #[inline]
+    pub fn try_resolve(&self, symbol: S) -> Result<&I, ResolveError> {
+        self.resolve(symbol).ok_or(ResolveError::OutOfRange {
+            index: symbol.to_usize(),
+            len: self.len(),
+        })
+    }
+
+    /// Returns the string for the given `symbol` without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the [`Interner`].
+    #[inline]
+    pub unsafe fn resolve_unchecked(&self, symbol: S) -> &I {
+        unsafe { self.backend.resolve_unchecked(symbol) }
+    }
+
+    /// Returns an owned copy of the string for the given `symbol` if any,
+    /// like a [`String`](alloc::string::String) for [`str`] or a [`CString`] for [`CStr`].
+    ///
+    /// The copy doesn't borrow the interner, so it can outlive it or be moved elsewhere.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let hello = interner.intern("hello");
+    /// let owned: String = interner.resolve_owned(hello).unwrap();
+    /// drop(interner);
+    /// assert_eq!(owned, "hello");
+    /// ```
+    #[inline]
+    pub fn resolve_owned(&self, symbol: S) -> Option<I::Owned>
+    where
+        I: ToOwned,
+    {
+        self.resolve(symbol).map(ToOwned::to_owned)
+    }
+
+    /// Returns the length of the string for the given `symbol` if any.
+    ///
+    /// The length is counted in [`Intern::Primitive`] elements, so it is the number
+    /// of bytes for [`str`], but the number of `char`s for `[char]`.
+    /// The built-in backends compute it without reading the string contents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let symbol = interner.intern("hello");
+    /// assert_eq!(interner.byte_len(symbol), Some(5));
+    /// ```
+    #[inline]
+    pub fn byte_len(&self, symbol: S) -> Option<usize> {
+        self.backend.byte_len(symbol)
+    }
+
+    /// Returns the length of the string for the given `symbol` without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the [`Interner`].
+    #[inline]
+    pub unsafe fn byte_len_unchecked(&self, symbol: S) -> usize {
+        unsafe { self.backend.byte_len_unchecked(symbol) }
+    }
+
+    /// Clears `out` and fills it with the strings for the given `symbols`.
+    ///
+    /// Reuses the allocation of `out`, which is useful when resolving
+    /// many batches of symbols.
+    ///
+    /// Returns `false` if any of the `symbols` is not valid for the [`Interner`].
+    /// In this case `out` contains only the strings for the symbols before it.
+    #[inline]
+    pub fn resolve_into<'a>(&'a self, symbols: &[S], out: &mut Vec<&'a I>) -> bool {
+        out.clear();
+        out.reserve(symbols.len());
+        for &symbol in symbols {
+            let Some(string) = self.backend.resolve(symbol) else {
+                return false;
+            };
+            out.push(string);
+        }
+        true
+    }
+
+    /// Clears `out` and fills it with the strings for the given `symbols`
+    /// without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbols`
+    /// that are valid for the [`Interner`].
+    #[inline]
+    pub unsafe fn resolve_into_unchecked<'a>(&'a self, symbols: &[S], out: &mut Vec<&'a I>) {
+        out.clear();
+        out.extend(symbols.iter().map(|&symbol| {
+            // SAFETY: The function is marked unsafe so that the caller guarantees
+            //         that required invariants are checked.
+            unsafe { self.backend.resolve_unchecked(symbol) }
+        }));
+    }
+
+    /// Returns an iterator that lazily yields the strings for the given `symbols`,
+    /// or `None` for the symbols that are not valid for the [`Interner`].
+    ///
+    /// See [`Interner::resolve_into`] to collect the strings into a reused vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let symbols = [interner.intern("a"), interner.intern("b"), interner.intern("a")];
+    /// let strings: Option<Vec<&str>> = interner.resolve_many(&symbols).collect();
+    /// assert_eq!(strings.unwrap(), ["a", "b", "a"]);
+    /// ```
+    #[inline]
+    pub fn resolve_many<'a>(
+        &'a self,
+        symbols: &'a [S],
+    ) -> impl ExactSizeIterator<Item = Option<&'a I>> + FusedIterator {
+        symbols.iter().map(|&symbol| self.backend.resolve(symbol))
+    }
+
+    /// Returns an iterator that lazily yields the strings for the given `symbols`
+    /// without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbols`
+    /// that are all valid for the [`Interner`].
+    #[inline]
+    pub unsafe fn resolve_many_unchecked<'a>(
+        &'a self,
+        symbols: &'a [S],
+    ) -> impl ExactSizeIterator<Item = &'a I> + FusedIterator {
+        symbols.iter().map(|&symbol| {
+            // SAFETY: The function is marked unsafe so that the caller guarantees
+            //         that required invariants are checked.
+            unsafe { self.backend.resolve_unchecked(symbol) }
+        })
+    }
+
+    /// Returns an iterator that yields all interned strings and their symbols.
+    #[inline]
+    pub fn iter(&self) -> B::Iter<'_> {
+        self.backend.iter()
+    }
+
+    /// Returns an iterator that yields the symbols of all interned strings.
+    #[inline]
+    pub fn symbols(&self) -> impl ExactSizeIterator<Item = S> + FusedIterator + use<I, S, H, B> {
+        (0..self.len()).map(expect_valid_symbol)
+    }
+
+    /// Returns an iterator that yields all interned strings.
+    #[inline]
+    pub fn strings(&self) -> impl ExactSizeIterator<Item = &I> + FusedIterator {
+        self.backend.iter().map(|(_, string)| string)
+    }
+
+    /// Returns an iterator that yields all interned strings and their symbols,
+    /// in lexicographic order of the strings.
+    ///
+    /// Unlike [`Interner::iter`], this collects and sorts all strings up front,
+    /// so it allocates and takes `O(n log n)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let interner = DefaultStringInterner::from_iter(["cc", "aa", "bb"]);
+    /// let sorted: Vec<&str> = interner.sorted_iter().map(|(_, string)| string).collect();
+    /// assert_eq!(sorted, ["aa", "bb", "cc"]);
+    /// ```
+    pub fn sorted_iter(&self) -> impl ExactSizeIterator<Item = (S, &I)> + FusedIterator
+    where
+        I: Ord,
+    {
+        let mut entries: Vec<(S, &I)> = self.backend.iter().collect();
+        // The strings are unique, so the order is the same as of a stable sort.
+        entries.sort_unstable_by_key(|&(_, string)| string);
+        entries.into_iter()
+    }
+
+    /// Turns the interner into a [`FrozenInterner`], which can no longer intern strings.
+    ///
+    /// Use [`FrozenInterner::thaw`] to get the interner back.
+    #[inline]
+    pub fn freeze(self) -> FrozenInterner<I, S, H, B> {
+        FrozenInterner::new(self)
+    }
+
+    /// Clears the deduplication map and fills it again from the strings and hashes
+    /// stored in the backend.
+    ///
+    /// This is needed only if the interner was created from parts that don't include
+    /// the deduplication map, to make [`Interner::get`] and [`Interner::intern`] work again.
+    ///
+    /// This is an `O(n)` operation. If the backend caches hashes, the strings are not rehashed,
+    /// so the cached hashes must be the ones produced by the hasher of the interner,
+    /// otherwise look-ups will miss the interned strings.
+    pub fn rebuild_dedup(&mut self) {
+        self.dedup.clear();
+        // All strings in the backend are unique, so they don't have to be compared.
+        for (symbol, string) in self.backend.iter() {
+            // SAFETY: This is safe because we only operate on symbols that
+            //         we receive from our backend making them valid.
+            let hash = unsafe {
+                self.backend
+                    .hash_unchecked(symbol, |_| make_hash(&self.hasher, string))
+            };
+            self.dedup.insert_unique(hash, symbol, |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe {
+                    self.backend
+                        .hash_unchecked(*symbol, |string| make_hash(&self.hasher, string))
+                }
+            });
+        }
+    }
+
+    /// Replaces the hasher of the interner with the given `hasher`.
+    ///
+    /// Recomputes the hashes of all interned strings with the new `hasher`,
+    /// replaces the hashes cached by the backend, and rebuilds the deduplication map.
+    /// The symbols of the interned strings stay the same.
+    ///
+    /// This is an `O(n)` operation.
+    pub fn rehash_with<H2: BuildHasher>(self, hasher: H2) -> Interner<I, S, H2, B> {
+        let mut backend = self.backend;
+        backend.rehash(|string| make_hash(&hasher, string));
+        let mut interner = Interner {
+            dedup: self.dedup,
+            hasher,
+            backend,
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: self.generation,
+        };
+        interner.rebuild_dedup();
+        interner
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H, B: HashedBackend<I, S>> Interner<I, S, H, B> {
+    /// Returns cached hash of the string for the given `symbol`.
+    pub fn get_hash(&self, symbol: S) -> Option<u64> {
+        self.backend.get_hash(symbol)
+    }
+
+    /// Returns cached hash of the string for the given `symbol` without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the [`Interner`].
+    pub unsafe fn get_hash_unchecked(&self, symbol: S) -> u64 {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { self.backend.get_hash_unchecked(symbol) }
+    }
+
+    /// Returns the string for the given `symbol` and its cached hash if any.
+    ///
+    /// Same as calling [`Interner::resolve`] and [`Interner::get_hash`],
+    /// but checks the `symbol` only once.
+    #[inline]
+    pub fn resolve_with_hash(&self, symbol: S) -> Option<(&I, u64)> {
+        self.backend.resolve_with_hash(symbol)
+    }
+
+    /// Returns the string for the given `symbol` and its cached hash
+    /// without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the [`Interner`].
+    #[inline]
+    pub unsafe fn resolve_with_hash_unchecked(&self, symbol: S) -> (&I, u64) {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { self.backend.resolve_with_hash_unchecked(symbol) }
+    }
+
+    /// Returns an iterator that yields all interned strings, their symbols, and hashes.
+    #[inline]
+    pub fn iter_with_hashes(&self) -> B::IterWithHashes<'_> {
+        self.backend.iter_with_hashes()
+    }
+
+    /// Returns an iterator that yields the hashes of all interned strings.
+    #[inline]
+    pub fn hashes(&self) -> impl ExactSizeIterator<Item = u64> + FusedIterator {
+        self.backend.hashes()
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H, O: Offset, const CACHE_HASHES: bool>
+    Interner<I, S, H, StringBackend<I, S, O, CACHE_HASHES>>
+{
+    /// Creates a new empty [`Interner`] with the given hasher in a constant expression,
+    /// like the initializer of a `static`.
+    ///
+    /// Nothing is allocated until the first string is interned.
+    /// The [`DefaultHashBuilder`] can't be created in constant expressions,
+    /// because it is seeded randomly at runtime, so the `hash_builder` has to be
+    /// constructible in a `const` context, like a [`FixedHashBuilder`].
+    /// See [`Interner::const_with_seed`] for a shorthand.
+    ///
+    /// Only the [`StringBackend`] supports this, as other backends
+    /// have no `const` constructors.
+    #[inline]
+    pub const fn const_with_hasher(hash_builder: H) -> Self {
+        Interner {
+            dedup: HashTable::new(),
+            hasher: hash_builder,
+            backend: StringBackend::new(),
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
+        }
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, O: Offset, const CACHE_HASHES: bool>
+    Interner<I, S, FixedHashBuilder, StringBackend<I, S, O, CACHE_HASHES>>
+{
+    /// Creates a new empty [`Interner`] with a [`FixedHashBuilder`] seeded with `seed`
+    /// in a constant expression.
+    ///
+    /// This is the `const` version of [`Interner::with_seed`], with the same caveats
+    /// about fixed seeds. See [`Interner::const_with_hasher`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Mutex;
+    /// use string_hash_interner::{DefaultSymbol, FixedHashBuilder, StringInterner};
+    ///
+    /// static NAMES: Mutex<StringInterner<DefaultSymbol, FixedHashBuilder>> =
+    ///     Mutex::new(StringInterner::const_with_seed(0));
+    ///
+    /// let hello = NAMES.lock().unwrap().intern("hello");
+    /// assert_eq!(NAMES.lock().unwrap().resolve(hello), Some("hello"));
+    /// ```
+    #[inline]
+    pub const fn const_with_seed(seed: u64) -> Self {
+        Self::const_with_hasher(FixedHashBuilder::with_seed(seed))
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H, O: Offset> Interner<I, S, H, StringBackend<I, S, O>> {
+    /// Returns a read-only [`ResolveTable`] view over the interned strings.
+    #[inline]
+    pub fn resolve_table(&self) -> ResolveTable<'_, I, S, O> {
+        self.backend.as_table()
+    }
+
+    /// Returns a read-only [`ResolveIndex`] view over the interned strings,
+    /// which stores the span of every string to resolve symbols with a single read.
+    ///
+    /// Takes `O(n)` to build. Returns `None` if the buffer holds more than
+    /// [`u32::MAX`] elements, since the spans are stored as `u32`s.
+    pub fn build_resolve_index(&self) -> Option<ResolveIndex<'_, I, S>> {
+        let buffer = self.backend.buffer();
+        u32::try_from(buffer.len()).ok()?;
+        let spans = (0..self.backend.as_table().len())
+            .map(|index| {
+                // SAFETY: All indices below the number of strings are valid for the backend.
+                let (from, to) = unsafe { self.backend.span_unchecked(index) };
+                // The ends are at most the length of the buffer, which fits into a `u32`.
+                (from as u32, (to - from) as u32)
+            })
+            .collect();
+        // SAFETY: The spans are taken from the backend, so they delimit valid strings.
+        Some(unsafe { ResolveIndex::new(buffer, spans) })
+    }
+
+    /// Returns an iterator that yields the interned strings and their symbols,
+    /// starting at the given `symbol`.
+    ///
+    /// Useful to process only the strings that were interned after `symbol`,
+    /// without walking over the preceding ones.
+    /// The iterator is empty if `symbol` is not valid for the [`Interner`].
+    #[inline]
+    pub fn iter_from(&self, symbol: S) -> Iter<'_, I, S, O> {
+        self.backend.iter_from(symbol.to_usize())
+    }
+
+    /// Returns the contents of all interned strings, stored contiguously
+    /// in order of their symbols.
+    ///
+    /// Use [`Interner::span`] to find a string in it.
+    #[inline]
+    pub fn buffer(&self) -> &[I::Primitive] {
+        self.backend.buffer()
+    }
+
+    /// Returns the half-open range of the string for the given `symbol`
+    /// in [`Interner::buffer`] if any.
+    ///
+    /// The range is counted in [`Intern::Primitive`] elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// interner.intern("hello");
+    /// let world = interner.intern("world");
+    /// let (from, to) = interner.span(world).unwrap();
+    /// assert_eq!((from, to), (5, 10));
+    /// assert_eq!(&interner.buffer()[from..to], b"world");
+    /// ```
+    #[inline]
+    pub fn span(&self, symbol: S) -> Option<(usize, usize)> {
+        self.backend.span(symbol.to_usize())
+    }
+
+    /// Returns the half-open range of the string for the given `symbol`
+    /// in [`Interner::buffer`] without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the [`Interner`].
+    #[inline]
+    pub unsafe fn span_unchecked(&self, symbol: S) -> (usize, usize) {
+        unsafe { self.backend.span_unchecked(symbol.to_usize()) }
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher, O: Offset>
+    Interner<I, S, H, StringBackend<I, S, O>>
+{
+    /// Reinterprets the interned strings as strings of type `J`, without copying them.
+    ///
+    /// Hashes are recomputed, since different string types may hash differently.
+    ///
+    /// # Safety
+    ///
+    /// Every interned string must be valid to be reinterpreted as `J` using [`Intern::from_bytes`].
+    unsafe fn cast<J>(self) -> Interner<J, S, H, StringBackend<J, S, O>>
+    where
+        J: Intern<Primitive = I::Primitive> + ?Sized,
+    {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let mut backend = unsafe { self.backend.cast::<J>() };
+        backend.rehash(|string| make_hash(&self.hasher, string));
+        let mut interner = Interner {
+            dedup: self.dedup,
+            hasher: self.hasher,
+            backend,
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: self.generation,
+        };
+        interner.rebuild_dedup();
+        interner
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher> Interner<I, S, H> {
+    /// Creates an interner from the parts returned by [`Interner::as_parts`]
+    /// or [`Interner::into_parts`].
+    ///
+    /// The vectors are moved into the interner without copying the strings.
+    ///
+    /// The deduplication map is rebuilt from the given `hashes`, without rehashing
+    /// the strings, so they must be the hashes produced by `hasher`. Otherwise
+    /// look-ups will miss the interned strings. The strings should be unique,
+    /// otherwise they keep separate symbols.
+    ///
+    /// # Safety
+    ///
+    /// - `ends` must be monotonically increasing, and the last end must equal `buffer.len()`.
+    /// - Every span between two consecutive ends must be a valid `I`.
+    /// - `hashes` must have the same length as `ends`.
+    /// - The number of strings must be representable by the symbol type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::{DefaultSymbol, FixedHashBuilder, StringInterner};
+    ///
+    /// type Interner = StringInterner<DefaultSymbol, FixedHashBuilder>;
+    ///
+    /// let interner = Interner::from_iter(["hello", "world"]);
+    /// let (buffer, ends, hashes) = interner.as_parts();
+    /// let (buffer, ends, hashes) = (buffer.to_vec(), ends.to_vec(), hashes.to_vec());
+    ///
+    /// // SAFETY: The parts come from a valid interner with the same hasher.
+    /// let restored =
+    ///     unsafe { Interner::from_parts(buffer, ends, hashes, FixedHashBuilder::default()) };
+    /// assert_eq!(restored.get("world"), interner.get("world"));
+    /// ```
+    pub unsafe fn from_parts(
+        buffer: Vec<I::Primitive>,
+        ends: Vec<usize>,
+        hashes: Vec<u64>,
+        hasher: H,
+    ) -> Self {
+        let mut interner = Interner {
+            dedup: HashTable::new(),
+            hasher,
+            // SAFETY: The function is marked unsafe so that the caller guarantees
+            //         that required invariants are checked.
+            backend: unsafe { StringBackend::from_parts(buffer, ends, hashes) },
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
+        };
+        interner.rebuild_dedup();
+        interner
+    }
+
+    /// Returns the buffer of all interned strings, the end of every string in it,
+    /// and the cached hash of every string, in order of their symbols.
+    ///
+    /// The start of a string is the end of the previous one, or `0` for the first one.
+    /// The parts can be turned back into an interner with [`Interner::from_parts`].
+    #[inline]
+    pub fn as_parts(&self) -> (&[I::Primitive], &[usize], &[u64]) {
+        self.backend.as_parts()
+    }
+
+    /// Returns the buffer of all interned strings, the end of every string in it,
+    /// the cached hash of every string, and the hasher, consuming the interner.
+    ///
+    /// Same as [`Interner::as_parts`], but moves the vectors out of the interner
+    /// without copying them, so they can be kept in another data structure.
+    /// The deduplication map is dropped, and [`Interner::from_parts`] rebuilds it
+    /// from the hashes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let interner = DefaultStringInterner::from_iter(["hello", "world"]);
+    /// let (buffer, ends, hashes, hasher) = interner.into_parts();
+    /// assert_eq!(buffer, b"helloworld");
+    /// assert_eq!(ends, [5, 10]);
+    ///
+    /// // SAFETY: The parts come from a valid interner, together with its hasher.
+    /// let restored = unsafe { DefaultStringInterner::from_parts(buffer, ends, hashes, hasher) };
+    /// assert_eq!(restored.resolve(restored.get("world").unwrap()), Some("world"));
+    /// ```
+    #[inline]
+    pub fn into_parts(self) -> (Vec<I::Primitive>, Vec<usize>, Vec<u64>, H) {
+        let (buffer, ends, hashes) = self.backend.into_parts();
+        (buffer, ends, hashes, self.hasher)
+    }
+
+    /// Creates an interner from a `buffer` of strings delimited by `ends`,
+    /// which may contain duplicates.
+    ///
+    /// Duplicates are removed by moving the unique strings towards the start of the `buffer`.
+    /// Returns the interner and the symbols of all strings in order.
+    ///
+    /// # Safety
+    ///
+    /// `ends` must be monotonically increasing, every end must be at most `buffer.len()`,
+    /// and every span must be a valid `I`.
+    pub(crate) unsafe fn from_pushed(
+        mut buffer: Vec<I::Primitive>,
+        pushed_ends: &[usize],
+        hasher: H,
+    ) -> (Self, Vec<S>) {
+        let mut dedup = HashTable::with_capacity(pushed_ends.len());
+        let mut ends: Vec<usize> = Vec::with_capacity(pushed_ends.len());
+        let mut hashes = Vec::with_capacity(pushed_ends.len());
+        let mut symbols = Vec::with_capacity(pushed_ends.len());
+
+        let mut from = 0;
+        for &to in pushed_ends {
+            // SAFETY: The function is marked unsafe so that the caller guarantees
+            //         that required invariants are checked.
+            let string = unsafe { I::from_bytes(&buffer[from..to]) };
+            let hash = make_hash(&hasher, string);
+            let entry = dedup.entry(
+                hash,
+                |symbol: &S| {
+                    // SAFETY: The unique strings are moved as a whole, keeping them valid.
+                    string
+                        == unsafe {
+                            ResolveTable::new(&buffer, &ends, &hashes).resolve_unchecked(*symbol)
+                        }
+                },
+                |symbol| hashes[symbol.to_usize()],
+            );
+            let symbol = match entry {
+                hash_table::Entry::Occupied(occupied) => *occupied.get(),
+                hash_table::Entry::Vacant(vacant) => {
+                    let start = ends.last().copied().unwrap_or(0);
+                    buffer.copy_within(from..to, start);
+                    let symbol = expect_valid_symbol(ends.len());
+                    ends.push(start + (to - from));
+                    hashes.push(hash);
+                    vacant.insert(symbol);
+                    symbol
+                }
+            };
+            symbols.push(symbol);
+            from = to;
+        }
+        buffer.truncate(ends.last().copied().unwrap_or(0));
+
+        let interner = Interner {
+            dedup,
+            hasher,
+            // SAFETY: The unique strings were moved to the start of the buffer in order.
+            backend: unsafe { StringBackend::from_parts(buffer, ends, hashes) },
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
+        };
+        (interner, symbols)
+    }
+
+    /// Creates an interner from the strings stored in the `backend`.
+    ///
+    /// The hashes cached by the `backend` are reused if the hash of its first string
+    /// matches the one produced by `hasher`, otherwise all strings are rehashed.
+    ///
+    /// The strings in the `backend` must be unique, otherwise they keep separate symbols.
+    #[cfg(any(feature = "serde", feature = "rkyv"))]
+    pub(crate) fn from_backend(mut backend: StringBackend<I, S>, hasher: H) -> Self {
+        if let Some((_, string, hash)) = backend.iter_with_hashes().next()
+            && make_hash(&hasher, string) != hash
+        {
+            backend.rehash(|string| make_hash(&hasher, string));
+        }
+        let mut interner = Interner {
+            dedup: HashTable::new(),
+            hasher,
+            backend,
+            marker: PhantomData,
+            #[cfg(debug_assertions)]
+            generation: 0,
+        };
+        interner.rebuild_dedup();
+        interner
+    }
+}
+
+impl<S: Symbol, H: BuildHasher, O: Offset> Interner<[u8], S, H, StringBackend<[u8], S, O>> {
+    /// Converts the interner into an [`Interner`] of [`str`]'s,
+    /// if all of the interned byte strings are valid UTF-8.
+    ///
+    /// The strings are not copied, and the symbols stay the same.
+    /// Hashes are recomputed, since [`str`] and `[u8]` hash differently.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first interned string that is not valid UTF-8.
+    #[allow(clippy::type_complexity)]
+    pub fn into_str_interner(
+        self,
+    ) -> Result<Interner<str, S, H, StringBackend<str, S, O>>, Utf8Error> {
+        for (_symbol, bytes) in self.iter() {
+            core::str::from_utf8(bytes)?;
+        }
+        // SAFETY: We just checked that every interned string is valid UTF-8.
+        Ok(unsafe { self.cast::<str>() })
+    }
+}
+
+impl<S: Symbol, H: BuildHasher, O: Offset> Interner<str, S, H, StringBackend<str, S, O>> {
+    /// Converts the interner into an [`Interner`] of byte strings.
+    ///
+    /// The strings are not copied, and the symbols stay the same.
+    /// Hashes are recomputed, since [`str`] and `[u8]` hash differently.
+    pub fn into_byte_interner(self) -> Interner<[u8], S, H, StringBackend<[u8], S, O>> {
+        // SAFETY: Every byte string is valid for `[u8]`.
+        unsafe { self.cast::<[u8]>() }
+    }
+}
+
+impl<S: Symbol, H: BuildHasher, O: Offset, const CACHE_HASHES: bool>
+    Interner<str, S, H, StringBackend<str, S, O, CACHE_HASHES>>
+{
+    /// Interns the string formatted from the given `args`.
+    ///
+    /// The string is formatted right into the buffer of the backend,
+    /// and removed from it again if it was already interned,
+    /// so no intermediate [`String`](alloc::string::String) is allocated.
+    /// See the [`intern_fmt!`](crate::intern_fmt!) macro for a shorthand.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type, or if a formatting trait implementation returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let (file, line) = ("main.rs", 42);
+    /// let symbol = interner.intern_fmt(format_args!("{file}:{line}"));
+    /// assert_eq!(interner.resolve(symbol), Some("main.rs:42"));
+    /// assert_eq!(interner.get("main.rs:42"), Some(symbol));
+    /// ```
+    #[inline]
+    pub fn intern_fmt(&mut self, args: fmt::Arguments<'_>) -> S {
+        self.try_intern_fmt(args)
+            .expect("encountered invalid symbol")
+    }
+
+    /// Interns the string formatted from the given `args`.
+    ///
+    /// See [`Interner::intern_fmt`].
+    ///
+    /// # Errors
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type, or if the buffer of the backend would overflow.
+    /// The interner is left unchanged in this case.
+    ///
+    /// # Panics
+    ///
+    /// If a formatting trait implementation returns an error.
+    pub fn try_intern_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<S, InternError> {
+        if let Some(string) = args.as_str() {
+            return self.try_intern(string);
+        }
+        self.backend.push_fmt(args);
+        self.try_intern_pushed()
+    }
+
+    /// Interns the string made of the given `chars`.
+    ///
+    /// The characters are encoded right into the buffer of the backend,
+    /// and removed from it again if the string was already interned,
+    /// so no intermediate [`String`](alloc::string::String) is allocated.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let symbol = interner.intern_chars("hello".chars().rev());
+    /// assert_eq!(interner.resolve(symbol), Some("olleh"));
+    /// assert_eq!(interner.intern("olleh"), symbol);
+    /// ```
+    #[inline]
+    pub fn intern_chars(&mut self, chars: impl IntoIterator<Item = char>) -> S {
+        self.try_intern_chars(chars)
+            .expect("encountered invalid symbol")
+    }
+
+    /// Interns the string made of the given `chars`.
+    ///
+    /// See [`Interner::intern_chars`].
+    ///
+    /// # Errors
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type, or if the buffer of the backend would overflow.
+    /// The interner is left unchanged in this case.
+    pub fn try_intern_chars(
+        &mut self,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<S, InternError> {
+        self.backend.push_chars(chars);
+        self.try_intern_pushed()
+    }
+}
+
+impl<S: Symbol, H: BuildHasher, O: Offset, const CACHE_HASHES: bool>
+    Interner<[u8], S, H, StringBackend<[u8], S, O, CACHE_HASHES>>
+{
+    /// Interns the byte string made of the concatenation of the given `chunks`.
+    ///
+    /// The chunks are copied right into the buffer of the backend,
+    /// and removed from it again if the byte string was already interned,
+    /// so they don't have to be concatenated into an intermediate [`Vec`] first.
+    /// The concatenation is hashed as a whole, so it gets the same symbol
+    /// as when interning it in one piece.
+    ///
+    /// # Panics
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::Interner;
+    ///
+    /// let mut interner = <Interner<[u8]>>::new();
+    /// let symbol = interner.intern_chunks([&b"GET "[..], b"/index", b".html"]);
+    /// assert_eq!(interner.resolve(symbol), Some(&b"GET /index.html"[..]));
+    /// assert_eq!(interner.intern(b"GET /index.html"), symbol);
+    /// ```
+    #[inline]
+    pub fn intern_chunks<'c>(&mut self, chunks: impl IntoIterator<Item = &'c [u8]>) -> S {
+        self.try_intern_chunks(chunks)
+            .expect("encountered invalid symbol")
+    }
+
+    /// Interns the byte string made of the concatenation of the given `chunks`.
+    ///
+    /// See [`Interner::intern_chunks`].
+    ///
+    /// # Errors
+    ///
+    /// If the interner already interns the maximum number of strings possible
+    /// by the chosen symbol type, or if the buffer of the backend would overflow.
+    /// The interner is left unchanged in this case.
+    pub fn try_intern_chunks<'c>(
+        &mut self,
+        chunks: impl IntoIterator<Item = &'c [u8]>,
+    ) -> Result<S, InternError> {
+        self.backend.push_chunks(chunks);
+        self.try_intern_pushed()
+    }
+}
+
+impl<I, S, H, O, const CACHE_HASHES: bool> Interner<I, S, H, StringBackend<I, S, O, CACHE_HASHES>>
+where
+    I: Intern<Primitive = u8> + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+    O: Offset,
+{
+    /// Interns the string appended to the buffer of the backend,
+    /// or removes it again if it is already interned.
+    fn try_intern_pushed(&mut self) -> Result<S, InternError> {
+        let string = self.backend.pushed();
+        let hash = make_hash(&self.hasher, string);
+        let entry = self.dedup.entry(
+            hash,
+            |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                string == unsafe { self.backend.resolve_unchecked(*symbol) }
+            },
+            |symbol| {
+                // SAFETY: This is safe because we only operate on symbols that
+                //         we receive from our backend making them valid.
+                unsafe {
+                    self.backend
+                        .hash_unchecked(*symbol, |string| make_hash(&self.hasher, string))
+                }
+            },
+        );
+        match entry {
+            hash_table::Entry::Occupied(occupied) => {
+                self.backend.discard_pushed();
+                Ok(*occupied.get())
+            }
+            hash_table::Entry::Vacant(vacant) => {
+                let symbol = self.backend.try_store_pushed(hash)?;
+                vacant.insert(symbol);
+                Ok(symbol)
+            }
+        }
+    }
+}
+
+/// Interns a formatted string into the given interner, without allocating a `String`.
+///
+/// Takes the interner followed by the same arguments as [`format!`](alloc::format),
+/// and calls [`Interner::intern_fmt`] with them.
+///
+/// # Example
+///
+/// ```
+/// use string_hash_interner::{intern_fmt, DefaultStringInterner};
+///
+/// let mut interner = DefaultStringInterner::new();
+/// let symbol = intern_fmt!(interner, "{}:{}", "main.rs", 42);
+/// assert_eq!(interner.resolve(symbol), Some("main.rs:42"));
+/// ```
+#[macro_export]
+macro_rules! intern_fmt {
+    ($interner:expr, $($arg:tt)*) => {
+        $interner.intern_fmt(::core::format_args!($($arg)*))
+    };
+}
+
+impl<S: Symbol, H: BuildHasher, B: Backend<CStr, S>> Interner<CStr, S, H, B> {
+    /// Returns the symbol for the C string with the contents of the given Rust string if any.
+    ///
+    /// Doesn't allocate a [`CString`] unless `string` is long.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `string` contains an interior nul byte.
+    pub fn get_str(&self, string: &str) -> Result<Option<S>, NulError> {
+        /// Strings shorter than this are copied to a buffer on the stack.
+        const STACK_LEN: usize = 64;
+
+        if string.bytes().any(|byte| byte == 0) {
+            return Err(CString::new(string).unwrap_err());
+        }
+
+        let mut stack = [0; STACK_LEN];
+        let heap;
+        let bytes = if string.len() < STACK_LEN {
+            stack[..string.len()].copy_from_slice(string.as_bytes());
+            &stack[..=string.len()]
+        } else {
+            heap = [string.as_bytes(), &[0]].concat();
+            &heap[..]
+        };
+
+        // SAFETY: We checked that `string` has no nul bytes, and appended one at the end.
+        let cstr = unsafe { CStr::from_bytes_with_nul_unchecked(bytes) };
+        Ok(self.get(cstr))
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default, B: Backend<I, S>, T: AsRef<I>>
+    FromIterator<T> for Interner<I, S, H, B>
+{
+    fn from_iter<It>(iter: It) -> Self
+    where
+        It: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let (capacity, _) = iter.size_hint();
+        let mut interner = Self::with_capacity(capacity);
+        interner.extend(iter);
+        interner
+    }
+}
+
+impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default, B: Backend<I, S>, T: AsRef<I>>
+    Extend<T> for Interner<I, S, H, B>
 {
+    /// Interns all given strings, discarding their symbols.
+    ///
+    /// Use [`Interner::intern_all`] to get the symbols.
     fn extend<It>(&mut self, iter: It)
     where
         It: IntoIterator<Item = T>,
@@ -267,12 +2370,227 @@ impl<I: Intern + ?Sized, S: Symbol, H: BuildHasher + Default, T: AsRef<I>> Exten
     }
 }
 
-impl<'a, I: Intern + ?Sized, S: Symbol, H> IntoIterator for &'a Interner<I, S, H> {
+impl<I: Intern + ?Sized, S: Symbol, H, B: Backend<I, S>> Index<S> for Interner<I, S, H, B> {
+    type Output = I;
+
+    /// Returns the string for the given `symbol`.
+    ///
+    /// This is the panicking version of [`Interner::resolve`], for symbols
+    /// that are known to be valid for the [`Interner`].
+    ///
+    /// # Panics
+    ///
+    /// If the `symbol` is not valid for the [`Interner`].
+    #[inline]
+    #[track_caller]
+    fn index(&self, symbol: S) -> &I {
+        self.backend
+            .resolve(symbol)
+            .expect("symbol is not valid for the interner")
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, H, B: Backend<I, S>> IntoIterator
+    for &'a Interner<I, S, H, B>
+{
     type Item = (S, &'a I);
-    type IntoIter = Iter<'a, I, S>;
+    type IntoIter = B::Iter<'a>;
 
     #[cfg_attr(feature = "inline-more", inline)]
     fn into_iter(self) -> Self::IntoIter {
         self.backend.iter()
     }
 }
+
+impl<I, S, H, B> IntoIterator for Interner<I, S, H, B>
+where
+    I: Intern + ToOwned + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+    type Item = (S, I::Owned);
+    type IntoIter = IntoIter<I, S, B>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            len: self.dedup.len(),
+            index: 0,
+            backend: self.backend,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// An iterator that moves the interned symbols and owned strings out of an [`Interner`].
+///
+/// Created by the [`IntoIterator`] implementation of [`Interner`].
+pub struct IntoIter<I: Intern + ?Sized, S, B> {
+    backend: B,
+    index: usize,
+    len: usize,
+    marker: PhantomData<fn(&I) -> S>,
+}
+
+impl<I, S, B> Debug for IntoIter<I, S, B>
+where
+    I: Intern + ?Sized,
+    B: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoIter")
+            .field("backend", &self.backend)
+            .field("index", &self.index)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<I, S, B> Iterator for IntoIter<I, S, B>
+where
+    I: Intern + ToOwned + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+    type Item = (S, I::Owned);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        let symbol = expect_valid_symbol(self.index);
+        self.index += 1;
+
+        // SAFETY: This is safe because all symbols below `len` are valid for the backend.
+        let string = unsafe { self.backend.resolve_unchecked(symbol) };
+        Some((symbol, string.to_owned()))
+    }
+}
+
+impl<I, S, B> ExactSizeIterator for IntoIter<I, S, B>
+where
+    I: Intern + ToOwned + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+}
+
+impl<I, S, B> FusedIterator for IntoIter<I, S, B>
+where
+    I: Intern + ToOwned + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+}
+
+/// An iterator that removes the interned symbols and strings from an [`Interner`],
+/// yielding owned strings.
+///
+/// Created by [`Interner::drain`].
+pub struct Drain<'a, I: Intern + ?Sized, S: Symbol, H, B: Backend<I, S>> {
+    interner: &'a mut Interner<I, S, H, B>,
+    index: usize,
+    len: usize,
+}
+
+impl<I, S, H, B> Debug for Drain<'_, I, S, H, B>
+where
+    I: Intern + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Drain")
+            .field("index", &self.index)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<I, S, H, B> Iterator for Drain<'_, I, S, H, B>
+where
+    I: Intern + ToOwned + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+    type Item = (S, I::Owned);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        let symbol = expect_valid_symbol(self.index);
+        self.index += 1;
+
+        // SAFETY: This is safe because all symbols below `len` are valid for the backend.
+        let string = unsafe { self.interner.backend.resolve_unchecked(symbol) };
+        Some((symbol, string.to_owned()))
+    }
+}
+
+impl<I, S, H, B> ExactSizeIterator for Drain<'_, I, S, H, B>
+where
+    I: Intern + ToOwned + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+}
+
+impl<I, S, H, B> FusedIterator for Drain<'_, I, S, H, B>
+where
+    I: Intern + ToOwned + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+}
+
+impl<I, S, H, B> Drop for Drain<'_, I, S, H, B>
+where
+    I: Intern + ?Sized,
+    S: Symbol,
+    B: Backend<I, S>,
+{
+    fn drop(&mut self) {
+        self.interner.dedup.clear();
+        self.interner.backend.clear();
+        #[cfg(debug_assertions)]
+        {
+            self.interner.generation = self.interner.generation.wrapping_add(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DefaultStringInterner;
+
+    #[test]
+    fn rebuild_dedup_works() {
+        let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
+        let mut interner = DefaultStringInterner::from_iter(strings);
+        let symbols = strings.map(|s| interner.get(s).unwrap());
+
+        interner.dedup.clear();
+        assert_eq!(interner.get("aa"), None);
+
+        interner.rebuild_dedup();
+        assert_eq!(interner.len(), strings.len());
+        assert_eq!(strings.map(|s| interner.get(s).unwrap()), symbols);
+        assert_eq!(interner.intern("cc"), symbols[2]);
+        assert_eq!(interner.len(), strings.len());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{
+    backend::Backend,
+    intern::{FromPrimitives, Intern},
+    Interner, Symbol,
+};
+use alloc::vec::Vec;
+use core::hash::BuildHasher;
+use std::io::{self, Read, Write};
+
+/// The maximum number of strings reserved up front while reading,
+/// so that a corrupt count can't allocate an arbitrary amount of memory.
+const MAX_PREALLOCATED: usize = 4096;
+
+impl<I, S, H, B> Interner<I, S, H, B>
+where
+    I: Intern<Primitive = u8> + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+    B: Backend<I, S>,
+{
+    /// Writes all strings of the interner to `writer`, in the order of their symbols.
+    ///
+    /// The output can be read back with [`Interner::read_from`].
+    ///
+    /// # Format
+    ///
+    /// All integers are unsigned LEB128 varints: 7 bits per byte, least significant
+    /// group first, with the high bit set on every byte but the last.
+    ///
+    /// 1. The number of strings.
+    /// 2. For every string, in the order of the symbols:
+    ///    1. the number of bytes of the string, as returned by [`Intern::as_bytes`],
+    ///    2. the bytes of the string.
+    ///
+    /// No header, hashes or padding are written.
+    /// The writes are small, so wrap unbuffered writers into a [`BufWriter`](std::io::BufWriter).
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of `writer`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let interner = DefaultStringInterner::from_iter(["a", "bc"]);
+    /// let mut bytes = Vec::new();
+    /// interner.write_to(&mut bytes).unwrap();
+    /// assert_eq!(bytes, b"\x02\x01a\x02bc");
+    /// ```
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_varint(&mut writer, self.len() as u64)?;
+        for (_symbol, string) in self {
+            let bytes = string.as_bytes();
+            write_varint(&mut writer, bytes.len() as u64)?;
+            writer.write_all(bytes)?;
+        }
+        writer.flush()
+    }
+}
+
+impl<I, S, H, B> Interner<I, S, H, B>
+where
+    I: FromPrimitives<Primitive = u8> + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+    B: Backend<I, S>,
+{
+    /// Reads an interner from `reader` in the format written by [`Interner::write_to`].
+    ///
+    /// The strings are interned one by one while they are read, so only a single
+    /// string is held in memory besides the interner itself. Every string gets
+    /// the symbol with the index of its position in the input, and a duplicate string
+    /// gets the symbol of its first occurrence, like when deserializing with `serde`.
+    ///
+    /// The reads are small, so wrap unbuffered readers into a [`BufReader`](std::io::BufReader).
+    ///
+    /// # Errors
+    ///
+    /// - [`io::ErrorKind::InvalidData`] if a varint is malformed or a string is not a valid `I`.
+    /// - [`io::ErrorKind::UnexpectedEof`] if the input ends before all strings are read.
+    /// - [`io::ErrorKind::Other`] with an [`InternError`](crate::InternError)
+    ///   if a string can't be interned.
+    /// - Any error of `reader`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::BufReader;
+    /// use string_hash_interner::DefaultStringInterner;
+    ///
+    /// let mut interner = DefaultStringInterner::new();
+    /// let hello = interner.intern("hello");
+    /// let mut bytes = Vec::new();
+    /// interner.write_to(&mut bytes).unwrap();
+    ///
+    /// let restored =
+    ///     DefaultStringInterner::read_from(BufReader::new(&bytes[..]), Default::default()).unwrap();
+    /// assert_eq!(restored.resolve(hello), Some("hello"));
+    /// ```
+    pub fn read_from<R: Read>(mut reader: R, hasher: H) -> io::Result<Self> {
+        let count = read_len(&mut reader)?;
+        let mut interner = Self::with_capacity_and_hasher(count.min(MAX_PREALLOCATED), hasher);
+        let mut bytes = Vec::new();
+        for _ in 0..count {
+            let len = read_len(&mut reader)?;
+            bytes.clear();
+            // `take` grows the buffer as the bytes arrive, instead of trusting `len` up front.
+            (&mut reader).take(len as u64).read_to_end(&mut bytes)?;
+            if bytes.len() != len {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            let string = I::from_bytes_checked(&bytes)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid string"))?;
+            let hash = interner.hasher().hash_one(string);
+            interner
+                .try_intern_hashed(string, hash)
+                .map_err(io::Error::other)?;
+        }
+        Ok(interner)
+    }
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    let mut buf = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len])
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        let [byte] = byte;
+        let bits = u64::from(byte & 0x7f);
+        if bits << shift >> shift != bits {
+            break;
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "varint overflows u64",
+    ))
+}
+
+fn read_len<R: Read>(reader: &mut R) -> io::Result<usize> {
+    usize::try_from(read_varint(reader)?)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "length overflows usize"))
+}
@@ -93,8 +93,7 @@
 //! // Now, if we need to lookup the entry in the hashmap and we
 //! // only have the symbol, we don't need to recompute the hash.
 //!
-//! let string = interner.resolve(sym).unwrap();
-//! let hash = interner.get_hash(sym).unwrap();
+//! let (string, hash) = interner.resolve_with_hash(sym).unwrap();
 //!
 //! let (k, v) = hashmap
 //!     .raw_entry()
@@ -147,25 +146,87 @@ extern crate std;
 
 #[cfg(feature = "serde")]
 mod serde_impl;
+#[cfg(feature = "serde")]
+pub use serde_impl::{borrowed as serde_borrowed, compact as serde_compact};
+
+#[cfg(any(feature = "serde", feature = "std"))]
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(feature = "std")]
+    pub use std::sync::OnceLock;
+}
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+#[cfg(feature = "rkyv")]
+pub use rkyv_impl::{ArchivedInterner, ArchivedIter, InternerResolver};
 
-mod backend;
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+
+#[cfg(feature = "std")]
+mod concurrent;
+#[cfg(feature = "std")]
+pub use concurrent::ConcurrentInterner;
+#[cfg(feature = "std")]
+mod io_impl;
+
+pub mod backend;
+mod builder;
+mod case_insensitive;
+mod checkpoint;
+mod entry;
+mod error;
+mod frozen;
+mod hash_stats;
 mod intern;
 mod interner;
+mod memory_usage;
+mod resolve_index;
+mod resolve_table;
 pub mod symbol;
 
 #[doc(inline)]
 pub use self::{
-    backend::{Iter, IterWithHashes},
-    intern::Intern,
-    interner::Interner,
+    backend::{Backend, HashedBackend, Iter, IterWithHashes},
+    builder::InternerBuilder,
+    case_insensitive::CaseInsensitiveStr,
+    checkpoint::Checkpoint,
+    entry::{Entry, VacantEntry},
+    error::{InternError, ReserveError, ResolveError},
+    frozen::FrozenInterner,
+    hash_stats::HashStats,
+    intern::{FromPrimitives, Intern},
+    interner::{Drain, Interner, IntoIter},
+    memory_usage::MemoryUsage,
+    resolve_index::ResolveIndex,
+    resolve_table::ResolveTable,
     symbol::{DefaultSymbol, Symbol},
 };
 
 #[doc(inline)]
 pub use hashbrown::DefaultHashBuilder;
 
+/// Hash builder with a fixed seed, using the same hashing algorithm as [`DefaultHashBuilder`].
+///
+/// See [`Interner::with_seed`].
+pub type FixedHashBuilder = foldhash::fast::FixedState;
+
+use self::backend::StringBackend;
+
 /// [`Interner`] for [`str`]'s.
-pub type StringInterner<S = DefaultSymbol, H = DefaultHashBuilder> = Interner<str, S, H>;
+pub type StringInterner<S = DefaultSymbol, H = DefaultHashBuilder, B = StringBackend<str, S>> =
+    Interner<str, S, H, B>;
 
 /// [`StringInterner`] with default Symbol and Hasher.
 pub type DefaultStringInterner = StringInterner;
+
+/// [`Interner`] for [`CaseInsensitiveStr`]'s, mapping strings that differ only in ASCII case
+/// to the same symbol.
+pub type CaseInsensitiveInterner<
+    S = DefaultSymbol,
+    H = DefaultHashBuilder,
+    B = StringBackend<CaseInsensitiveStr, S>,
+> = Interner<CaseInsensitiveStr, S, H, B>;
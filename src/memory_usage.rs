@@ -0,0 +1,21 @@
+/// Heap memory held by an [`Interner`](crate::Interner), in bytes.
+///
+/// Returned by [`Interner::memory_usage`](crate::Interner::memory_usage).
+/// Counts the allocated capacity, including the part that is not used yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    /// Bytes allocated for the contents of the strings.
+    pub buffer_bytes: usize,
+    /// Bytes allocated for the index of the strings, like their ends and hashes.
+    pub ends_bytes: usize,
+    /// Bytes allocated for the deduplication map.
+    pub dedup_bytes: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the total number of allocated bytes.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.buffer_bytes + self.ends_bytes + self.dedup_bytes
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{backend::Backend, intern::Intern, Interner, Symbol};
+use alloc::{collections::LinkedList, vec::Vec};
+use core::hash::BuildHasher;
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+/// Interns all strings of a parallel iterator.
+///
+/// Interning needs exclusive access to the deduplication map and the buffer of strings,
+/// so the strings can't be interned concurrently. Instead, the strings are hashed in parallel
+/// and collected in order, then interned one by one with their precomputed hashes.
+/// The symbols are assigned in the order of the parallel iterator,
+/// like with [`Extend`], so no remapping is needed.
+///
+/// # Example
+///
+/// ```
+/// use rayon::prelude::*;
+/// use string_hash_interner::DefaultStringInterner;
+///
+/// let mut interner = DefaultStringInterner::new();
+/// interner.par_extend(["a", "b", "a"].into_par_iter());
+/// assert_eq!(interner.len(), 2);
+/// let a = interner.get("a").unwrap();
+/// assert_eq!(&interner[a], "a");
+/// ```
+impl<I, S, H, B, T> ParallelExtend<T> for Interner<I, S, H, B>
+where
+    I: Intern + ?Sized,
+    S: Symbol,
+    H: BuildHasher + Sync,
+    B: Backend<I, S>,
+    T: AsRef<I> + Send,
+{
+    fn par_extend<It>(&mut self, par_iter: It)
+    where
+        It: IntoParallelIterator<Item = T>,
+    {
+        let hasher = self.hasher();
+        let chunks = par_iter
+            .into_par_iter()
+            .map(|string| {
+                let hash = hasher.hash_one(string.as_ref());
+                (string, hash)
+            })
+            .fold(Vec::new, |mut chunk, item| {
+                chunk.push(item);
+                chunk
+            })
+            .map(|chunk| LinkedList::from([chunk]))
+            .reduce(LinkedList::new, |mut chunks, mut other| {
+                chunks.append(&mut other);
+                chunks
+            });
+        self.reserve(chunks.iter().map(Vec::len).sum());
+        for (string, hash) in chunks.into_iter().flatten() {
+            self.intern_with_hash(string, hash);
+        }
+    }
+}
+
+/// Creates an interner from a parallel iterator of strings.
+///
+/// See the [`ParallelExtend`] implementation for how the strings are interned.
+impl<I, S, H, B, T> FromParallelIterator<T> for Interner<I, S, H, B>
+where
+    I: Intern + ?Sized,
+    S: Symbol,
+    H: BuildHasher + Default + Send + Sync,
+    B: Backend<I, S> + Send,
+    T: AsRef<I> + Send,
+{
+    fn from_par_iter<It>(par_iter: It) -> Self
+    where
+        It: IntoParallelIterator<Item = T>,
+    {
+        let mut interner = Self::new();
+        interner.par_extend(par_iter);
+        interner
+    }
+}
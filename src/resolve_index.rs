@@ -0,0 +1,117 @@
+use crate::{intern::Intern, Symbol};
+use alloc::boxed::Box;
+use core::{fmt, fmt::Debug, marker::PhantomData};
+
+/// A read-only view over the contents of an interner with the span of every string
+/// stored next to each other.
+///
+/// Created with [`Interner::build_resolve_index`](crate::Interner::build_resolve_index).
+/// Resolving a symbol reads a single `(start, len)` pair of `u32`s, instead of
+/// the ends of the string and of the previous one, and doesn't branch on the first symbol.
+/// The view borrows the interner, so it can't get out of date: interning more strings
+/// requires dropping it and building it again.
+///
+/// Building the index takes `O(n)` and allocates 8 bytes per string.
+/// On the benchmark corpus of 100k strings of 5 bytes, resolution takes about 30 to 40%
+/// less time than with [`Interner::resolve_unchecked`](crate::Interner::resolve_unchecked),
+/// so it pays off in loops that resolve many symbols between two calls to intern.
+///
+/// # Example
+///
+/// ```
+/// use string_hash_interner::DefaultStringInterner;
+///
+/// let mut interner = DefaultStringInterner::new();
+/// let hello = interner.intern("hello");
+/// let index = interner.build_resolve_index().unwrap();
+/// assert_eq!(index.resolve(hello), Some("hello"));
+/// ```
+pub struct ResolveIndex<'a, I: Intern + ?Sized, S> {
+    buffer: &'a [I::Primitive],
+    /// Stores start and length of every string
+    spans: Box<[(u32, u32)]>,
+    marker: PhantomData<fn() -> (S, &'a I)>,
+}
+
+impl<I: Intern + ?Sized, S> Debug for ResolveIndex<'_, I, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolveIndex")
+            .field("spans", &self.spans)
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+impl<I: Intern + ?Sized, S> Clone for ResolveIndex<'_, I, S> {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer,
+            spans: self.spans.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol> ResolveIndex<'a, I, S> {
+    /// Creates a new [`ResolveIndex`] from the contiguous `buffer` of interned strings
+    /// and the `(start, len)` span of every string in it.
+    ///
+    /// # Safety
+    ///
+    /// Every span must be in bounds of `buffer` and must have been obtained
+    /// from [`Intern::as_bytes`] of a valid `I`.
+    #[inline]
+    pub(crate) unsafe fn new(buffer: &'a [I::Primitive], spans: Box<[(u32, u32)]>) -> Self {
+        Self {
+            buffer,
+            spans,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of strings in the index.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Returns `true` if the index has no strings.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the string associated to the span.
+    ///
+    /// # Safety
+    ///
+    /// Span must be valid within the [Self::buffer]
+    #[inline]
+    unsafe fn span_to_str(&self, (start, len): (u32, u32)) -> &'a I {
+        let start = start as usize;
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { I::from_bytes(self.buffer.get_unchecked(start..start + len as usize)) }
+    }
+
+    /// Returns the string for the given `symbol` if any.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&'a I> {
+        let span = *self.spans.get(symbol.to_usize())?;
+        // SAFETY: This span is guaranteed to be valid
+        unsafe { Some(self.span_to_str(span)) }
+    }
+
+    /// Returns the string for the given `symbol` without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the [`ResolveIndex`].
+    #[inline]
+    pub unsafe fn resolve_unchecked(&self, symbol: S) -> &'a I {
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe { self.span_to_str(*self.spans.get_unchecked(symbol.to_usize())) }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    backend::{Iter, IterWithHashes, Offset},
+    intern::Intern,
+    Symbol,
+};
+use core::{fmt, fmt::Debug, marker::PhantomData};
+
+/// A read-only view over the contents of an interner.
+///
+/// Supports only resolution of symbols and look-ups of cached hashes.
+/// Unlike [`Interner`](crate::Interner), it does not hold a deduplication map,
+/// so it does not allocate and is cheap to create from borrowed parts,
+/// for example from a memory-mapped file.
+///
+/// The ends of the strings are stored as [`Offset`]s of type `O`,
+/// matching the [`StringBackend`](crate::backend::StringBackend) the table was created from.
+pub struct ResolveTable<'a, I: Intern + ?Sized, S, O = usize> {
+    buffer: &'a [I::Primitive],
+    /// Stores end of every string
+    ends: &'a [O],
+    /// Stores hash of every string
+    hashes: &'a [u64],
+    marker: PhantomData<fn() -> (S, &'a I)>,
+}
+
+impl<I: Intern + ?Sized, S, O: Offset> Debug for ResolveTable<'_, I, S, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolveTable")
+            .field("ends", &self.ends)
+            .field("hashes", &self.hashes)
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+impl<I: Intern + ?Sized, S, O> Clone for ResolveTable<'_, I, S, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: Intern + ?Sized, S, O> Copy for ResolveTable<'_, I, S, O> {}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, O: Offset> ResolveTable<'a, I, S, O> {
+    /// Creates a new [`ResolveTable`] from the contiguous `buffer` of interned strings,
+    /// the `ends` of every string in it, and the `hashes` of the strings.
+    ///
+    /// # Safety
+    ///
+    /// - `ends` must be monotonically increasing, and every end must be
+    ///   at most `buffer.len()`.
+    /// - Every span of `buffer` delimited by `ends` must have been obtained
+    ///   from [`Intern::as_bytes`] of a valid `I`.
+    /// - `hashes` must have the same length as `ends`.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub unsafe fn new(buffer: &'a [I::Primitive], ends: &'a [O], hashes: &'a [u64]) -> Self {
+        Self {
+            buffer,
+            ends,
+            hashes,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of strings in the table.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if the table has no strings.
+    #[cfg_attr(feature = "inline-more", inline)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the string associated to the span.
+    ///
+    /// # Safety
+    ///
+    /// Span must be valid within the [Self::buffer]
+    unsafe fn span_to_str(&self, from: usize, to: usize) -> &'a I {
+        unsafe { I::from_bytes(&self.buffer[from..to]) }
+    }
+
+    /// Returns the string for the given `symbol` if any.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&'a I> {
+        let index = symbol.to_usize();
+        let to = self.ends.get(index)?.to_usize();
+
+        let from = self
+            .ends
+            .get(index.wrapping_sub(1))
+            .map(|end| end.to_usize())
+            .unwrap_or(0);
+
+        // SAFETY: This span is guaranteed to be valid
+        unsafe { Some(self.span_to_str(from, to)) }
+    }
+
+    /// Returns the string for the given `symbol` without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the [`ResolveTable`].
+    #[inline]
+    pub unsafe fn resolve_unchecked(&self, symbol: S) -> &'a I {
+        let index = symbol.to_usize();
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        let to = unsafe { self.ends.get_unchecked(index).to_usize() };
+        let from = self
+            .ends
+            .get(index.wrapping_sub(1))
+            .map(|end| end.to_usize())
+            .unwrap_or(0);
+
+        // SAFETY: This span is guaranteed to be valid
+        unsafe { self.span_to_str(from, to) }
+    }
+
+    /// Returns cached hash of the string for the given `symbol`.
+    #[inline]
+    pub fn get_hash(&self, symbol: S) -> Option<u64> {
+        self.hashes.get(symbol.to_usize()).copied()
+    }
+
+    /// Returns an iterator that yields all strings, their symbols, and hashes.
+    #[inline]
+    pub fn iter_with_hashes(&self) -> IterWithHashes<'a, I, S, O> {
+        IterWithHashes::new(self.buffer, self.ends, self.hashes)
+    }
+
+    /// Returns an iterator that yields all strings and their symbols.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, I, S, O> {
+        Iter::new(self.buffer, self.ends)
+    }
+}
+
+impl<'a, I: Intern + ?Sized, S: Symbol, O: Offset> IntoIterator for ResolveTable<'a, I, S, O> {
+    type Item = (S, &'a I);
+    type IntoIter = Iter<'a, I, S, O>;
+
+    #[cfg_attr(feature = "inline-more", inline)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{backend::StringBackend, intern::FromPrimitives, Interner, Symbol};
+use alloc::vec::Vec;
+use core::{fmt, hash::BuildHasher, iter::FusedIterator, marker::PhantomData};
+use rkyv::{
+    bytecheck::{CheckBytes, Verify},
+    munge::munge,
+    primitive::{ArchivedU64, ArchivedUsize, FixedUsize},
+    rancor::{fail, Fallible, Source},
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Deserialize, Place, Portable, Serialize,
+};
+
+/// An archived [`Interner`] with the default [`StringBackend`].
+///
+/// Stores the buffer of string contents, and the end and the hash of every string in it,
+/// like [`Interner::as_parts`]. The strings are resolved directly from the archived bytes,
+/// without copying them. Look-ups of strings need the deduplication map, which is not
+/// archived: deserialize the interner to rebuild it.
+///
+/// Validating the archive with [`rkyv::access`] checks every string, so it takes `O(n)`.
+///
+/// # Note
+///
+/// The ends are archived as `usize`s of the pointer width chosen for `rkyv`,
+/// which is 32 bits by default. Interners with larger buffers need one of its
+/// `pointer_width_*` features, otherwise serializing them fails.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::rancor::Error;
+/// use string_hash_interner::{ArchivedInterner, DefaultStringInterner, DefaultSymbol};
+///
+/// let mut interner = DefaultStringInterner::new();
+/// let hello = interner.intern("hello");
+///
+/// let bytes = rkyv::to_bytes::<Error>(&interner).unwrap();
+/// let archived = rkyv::access::<ArchivedInterner<str, DefaultSymbol>, Error>(&bytes).unwrap();
+/// assert_eq!(archived.resolve(hello), Some("hello"));
+///
+/// let restored: DefaultStringInterner = rkyv::deserialize::<_, Error>(archived).unwrap();
+/// assert_eq!(restored.get("hello"), Some(hello));
+/// ```
+///
+/// [`StringBackend`]: crate::backend::StringBackend
+#[derive(Portable, CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck, verify)]
+#[repr(C)]
+pub struct ArchivedInterner<I: ?Sized, S> {
+    buffer: ArchivedVec<u8>,
+    ends: ArchivedVec<ArchivedUsize>,
+    hashes: ArchivedVec<ArchivedU64>,
+    marker: PhantomData<fn(&I) -> S>,
+}
+
+impl<I: FromPrimitives<Primitive = u8> + ?Sized, S: Symbol> ArchivedInterner<I, S> {
+    /// Returns the number of archived strings.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if the archive contains no strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the string for the given `symbol` if any.
+    #[inline]
+    pub fn resolve(&self, symbol: S) -> Option<&I> {
+        let index = symbol.to_usize();
+        let to = self.ends.get(index)?.to_native() as usize;
+        // SAFETY: The index is in bounds of the ends.
+        unsafe { Some(self.span_to_str(index, to)) }
+    }
+
+    /// Returns the string for the given `symbol` without performing any checks.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to provide this method with `symbol`s
+    /// that are valid for the archive.
+    #[inline]
+    pub unsafe fn resolve_unchecked(&self, symbol: S) -> &I {
+        let index = symbol.to_usize();
+        // SAFETY: The function is marked unsafe so that the caller guarantees
+        //         that required invariants are checked.
+        unsafe {
+            let to = self.ends.get_unchecked(index).to_native() as usize;
+            self.span_to_str(index, to)
+        }
+    }
+
+    /// Returns the cached hash of the string for the given `symbol` if any.
+    #[inline]
+    pub fn get_hash(&self, symbol: S) -> Option<u64> {
+        self.hashes
+            .get(symbol.to_usize())
+            .map(|hash| hash.to_native())
+    }
+
+    /// Returns an iterator over the archived symbols and their strings.
+    #[inline]
+    pub fn iter(&self) -> ArchivedIter<'_, I, S> {
+        ArchivedIter {
+            archive: self,
+            index: 0,
+        }
+    }
+
+    /// Returns the string at `index`, which ends at `to`.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be in bounds of [Self::ends], and `to` must be its end.
+    unsafe fn span_to_str(&self, index: usize, to: usize) -> &I {
+        let from = self
+            .ends
+            .get(index.wrapping_sub(1))
+            .map(|end| end.to_native() as usize)
+            .unwrap_or(0);
+        // SAFETY: The ends and the spans were validated when the archive was accessed.
+        unsafe { I::from_bytes(self.buffer.get_unchecked(from..to)) }
+    }
+}
+
+impl<I, S> fmt::Debug for ArchivedInterner<I, S>
+where
+    I: FromPrimitives<Primitive = u8> + fmt::Debug + ?Sized,
+    S: Symbol + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the symbols and strings of an [`ArchivedInterner`].
+pub struct ArchivedIter<'a, I: ?Sized, S> {
+    archive: &'a ArchivedInterner<I, S>,
+    index: usize,
+}
+
+impl<'a, I: FromPrimitives<Primitive = u8> + ?Sized, S: Symbol> Iterator
+    for ArchivedIter<'a, I, S>
+{
+    type Item = (S, &'a I);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len();
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let symbol = S::try_from_usize(self.index)?;
+        let string = self.archive.resolve(symbol)?;
+        self.index += 1;
+        Some((symbol, string))
+    }
+}
+
+impl<I: FromPrimitives<Primitive = u8> + ?Sized, S: Symbol> ExactSizeIterator
+    for ArchivedIter<'_, I, S>
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.archive.len() - self.index
+    }
+}
+
+impl<I: FromPrimitives<Primitive = u8> + ?Sized, S: Symbol> FusedIterator
+    for ArchivedIter<'_, I, S>
+{
+}
+
+/// The error produced when an archived interner is invalid.
+#[derive(Debug)]
+struct InvalidArchive(&'static str);
+
+impl fmt::Display for InvalidArchive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl core::error::Error for InvalidArchive {}
+
+/// The error produced when the buffer of an interner is too large
+/// for the archived `usize`.
+#[derive(Debug)]
+struct BufferTooLarge;
+
+impl fmt::Display for BufferTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the buffer is too large for the pointer width of the archive")
+    }
+}
+
+impl core::error::Error for BufferTooLarge {}
+
+// SAFETY: Every span between two consecutive ends is checked to be a valid `I`,
+//         which is what the resolving methods rely on.
+unsafe impl<I, S, C> Verify<C> for ArchivedInterner<I, S>
+where
+    I: FromPrimitives<Primitive = u8> + ?Sized,
+    S: Symbol,
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    fn verify(&self, _context: &mut C) -> Result<(), C::Error> {
+        if self.ends.len() != self.hashes.len() {
+            fail!(InvalidArchive("the number of ends and hashes differ"));
+        }
+        if let Some(last) = self.len().checked_sub(1)
+            && S::try_from_usize(last).is_none()
+        {
+            fail!(InvalidArchive("too many strings for the symbol type"));
+        }
+        let mut from = 0;
+        for end in self.ends.iter() {
+            let to = end.to_native() as usize;
+            let Some(span) = self.buffer.get(from..to) else {
+                fail!(InvalidArchive("invalid end of an interned string"));
+            };
+            if I::from_bytes_checked(span).is_none() {
+                fail!(InvalidArchive("invalid interned string"));
+            }
+            from = to;
+        }
+        if from != self.buffer.len() {
+            fail!(InvalidArchive("invalid length of the buffer"));
+        }
+        Ok(())
+    }
+}
+
+/// The resolver for an archived [`Interner`].
+pub struct InternerResolver {
+    buffer: VecResolver,
+    ends: VecResolver,
+    hashes: VecResolver,
+}
+
+impl<I, S, H> Archive for Interner<I, S, H>
+where
+    I: FromPrimitives<Primitive = u8> + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+{
+    type Archived = ArchivedInterner<I, S>;
+    type Resolver = InternerResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        let (buffer, ends, hashes) = self.as_parts();
+        munge!(let ArchivedInterner {
+            buffer: out_buffer,
+            ends: out_ends,
+            hashes: out_hashes,
+            marker: _,
+        } = out);
+        ArchivedVec::resolve_from_slice(buffer, resolver.buffer, out_buffer);
+        ArchivedVec::resolve_from_slice(ends, resolver.ends, out_ends);
+        ArchivedVec::resolve_from_slice(hashes, resolver.hashes, out_hashes);
+    }
+}
+
+impl<I, S, H, T> Serialize<T> for Interner<I, S, H>
+where
+    I: FromPrimitives<Primitive = u8> + ?Sized,
+    S: Symbol,
+    H: BuildHasher,
+    T: Fallible + Allocator + Writer + ?Sized,
+    T::Error: Source,
+{
+    fn serialize(&self, serializer: &mut T) -> Result<Self::Resolver, T::Error> {
+        let (buffer, ends, hashes) = self.as_parts();
+        // The ends would be truncated when archived.
+        if FixedUsize::try_from(buffer.len()).is_err() {
+            fail!(BufferTooLarge);
+        }
+        Ok(InternerResolver {
+            buffer: ArchivedVec::serialize_from_slice(buffer, serializer)?,
+            ends: ArchivedVec::serialize_from_slice(ends, serializer)?,
+            hashes: ArchivedVec::serialize_from_slice(hashes, serializer)?,
+        })
+    }
+}
+
+/// Deserializing copies the strings and rebuilds the deduplication map.
+///
+/// The archived hashes are only reused if the hasher of the deserialized interner
+/// produces the same hashes. This is checked with the first string,
+/// and otherwise all strings are rehashed.
+impl<I, S, H, D> Deserialize<Interner<I, S, H>, D> for ArchivedInterner<I, S>
+where
+    I: FromPrimitives<Primitive = u8> + ?Sized,
+    S: Symbol,
+    H: BuildHasher + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, _deserializer: &mut D) -> Result<Interner<I, S, H>, D::Error> {
+        let buffer = self.buffer.to_vec();
+        let ends: Vec<usize> = self
+            .ends
+            .iter()
+            .map(|end| end.to_native() as usize)
+            .collect();
+        let hashes: Vec<u64> = self.hashes.iter().map(|hash| hash.to_native()).collect();
+        // SAFETY: The archive was validated when it was accessed.
+        let backend = unsafe { StringBackend::from_parts(buffer, ends, hashes) };
+        Ok(Interner::from_backend(backend, H::default()))
+    }
+}
@@ -5,7 +5,7 @@
 //! method returns [`Symbol`] types that allow to look-up the original string
 //! using [`Interner::resolve`](crate::Interner::resolve).
 
-use core::num::{NonZeroU16, NonZeroU32, NonZeroUsize};
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
 
 /// Types implementing this trait can be used as symbols for string interners.
 ///
@@ -17,6 +17,22 @@ use core::num::{NonZeroU16, NonZeroU32, NonZeroUsize};
 ///
 /// Optimal symbols allow for efficient comparisons and have a small memory footprint.
 pub trait Symbol: Copy + Eq {
+    /// The largest index the symbol can represent.
+    ///
+    /// An interner with this symbol type holds at most `MAX_INDEX + 1` strings,
+    /// interning more fails with [`InternError::SymbolOverflow`](crate::InternError::SymbolOverflow).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use string_hash_interner::{symbol::SymbolU8, Symbol};
+    ///
+    /// assert_eq!(SymbolU8::MAX_INDEX, 254);
+    /// assert!(SymbolU8::try_from_usize(SymbolU8::MAX_INDEX).is_some());
+    /// assert!(SymbolU8::try_from_usize(SymbolU8::MAX_INDEX + 1).is_none());
+    /// ```
+    const MAX_INDEX: usize;
+
     /// Creates a symbol from a `usize`.
     ///
     /// Returns `None` if `index` is out of bounds for the symbol.
@@ -43,6 +59,8 @@ where
 pub type DefaultSymbol = SymbolU32;
 
 impl Symbol for usize {
+    const MAX_INDEX: usize = usize::MAX;
+
     #[inline]
     fn try_from_usize(index: usize) -> Option<Self> {
         Some(index)
@@ -73,6 +91,16 @@ macro_rules! gen_symbol_for {
         }
 
         impl Symbol for $name {
+            // The largest value is taken by the last index shifted by one.
+            const MAX_INDEX: usize = {
+                let max = <$base_ty>::MAX - 1;
+                if max as u128 > usize::MAX as u128 {
+                    usize::MAX
+                } else {
+                    max as usize
+                }
+            };
+
             #[inline]
             fn try_from_usize(index: usize) -> Option<Self> {
                 <$base_ty>::try_from(index).ok().and_then(Self::new)
@@ -80,11 +108,18 @@ macro_rules! gen_symbol_for {
 
             #[inline]
             fn to_usize(self) -> usize {
-                self.value.get() as usize - 1
+                (self.value.get() - 1) as usize
             }
         }
     };
 }
+gen_symbol_for!(
+    /// Symbol that is 8-bit in size.
+    ///
+    /// Is space-optimized for used in `Option`.
+    /// Can represent up to 255 strings.
+    struct SymbolU8(NonZeroU8; u8);
+);
 gen_symbol_for!(
     /// Symbol that is 16-bit in size.
     ///
@@ -97,6 +132,22 @@ gen_symbol_for!(
     /// Is space-optimized for used in `Option`.
     struct SymbolU32(NonZeroU32; u32);
 );
+gen_symbol_for!(
+    /// Symbol that is 64-bit in size.
+    ///
+    /// Is space-optimized for used in `Option`.
+    /// Unlike [`SymbolUsize`], it stays 64-bit on targets with smaller pointers,
+    /// where it can't represent more strings than [`SymbolUsize`] though.
+    struct SymbolU64(NonZeroU64; u64);
+);
+gen_symbol_for!(
+    /// Symbol that is 128-bit in size.
+    ///
+    /// Is space-optimized for used in `Option`.
+    /// Can represent more strings than any interner can hold, it mostly exists
+    /// for symbols which are stored alongside other 128-bit values.
+    struct SymbolU128(NonZeroU128; u128);
+);
 gen_symbol_for!(
     /// Symbol that is the same size as a pointer (`usize`).
     ///
@@ -104,6 +155,140 @@ gen_symbol_for!(
     struct SymbolUsize(NonZeroUsize; usize);
 );
 
+/// Defines a new symbol type wrapping the given unsigned integer type.
+///
+/// Like the built-in symbols, the generated type stores the index shifted by one
+/// in a [`NonZero`](core::num::NonZero), so `Option` of it has the same size.
+/// It derives `Debug`, `Copy`, `Clone`, `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash`,
+/// and further attributes can be put in front of the definition.
+///
+/// Append `with serde` to also implement `Serialize` and `Deserialize` like the built-in
+/// symbols do. This needs the `serde` feature of this crate.
+///
+/// # Example
+///
+/// Distinct symbol types keep the symbols of different interners apart:
+///
+/// ```
+/// use string_hash_interner::{define_symbol, StringInterner};
+///
+/// define_symbol! {
+///     /// Identifies a source file.
+///     pub struct FileId(u32);
+/// }
+/// define_symbol!(pub struct IdentId(u16));
+///
+/// let mut files = StringInterner::<FileId>::new();
+/// let mut idents = StringInterner::<IdentId>::new();
+/// let main = files.intern("main.rs");
+/// let foo = idents.intern("foo");
+/// assert_eq!(files.resolve(main), Some("main.rs"));
+/// assert_eq!(idents.resolve(foo), Some("foo"));
+/// assert_eq!(size_of::<Option<IdentId>>(), 2);
+/// ```
+///
+/// Mixing them up doesn't compile:
+///
+/// ```compile_fail
+/// # use string_hash_interner::{define_symbol, StringInterner};
+/// # define_symbol!(pub struct FileId(u32));
+/// # define_symbol!(pub struct IdentId(u16));
+/// let mut files = StringInterner::<FileId>::new();
+/// let mut idents = StringInterner::<IdentId>::new();
+/// let main = files.intern("main.rs");
+/// idents.resolve(main);
+/// ```
+#[macro_export]
+macro_rules! define_symbol {
+    (
+        $( #[$attr:meta] )*
+        $vis:vis struct $name:ident($base_ty:ty) $( with $serde:ident )? $(;)?
+    ) => {
+        $( #[$attr] )*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name(::core::num::NonZero<$base_ty>);
+
+        impl $crate::Symbol for $name {
+            const MAX_INDEX: usize = {
+                let max = <$base_ty>::MAX - 1;
+                if max as u128 > usize::MAX as u128 {
+                    usize::MAX
+                } else {
+                    max as usize
+                }
+            };
+
+            #[inline]
+            fn try_from_usize(index: usize) -> ::core::option::Option<Self> {
+                <$base_ty as ::core::convert::TryFrom<usize>>::try_from(index)
+                    .ok()
+                    .and_then(|index| ::core::num::NonZero::new(index.wrapping_add(1)))
+                    .map(Self)
+            }
+
+            #[inline]
+            fn to_usize(self) -> usize {
+                (self.0.get() - 1) as usize
+            }
+        }
+
+        $( $crate::__define_symbol_impl!($serde $name, $base_ty); )?
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "serde")]
+macro_rules! __define_symbol_impl {
+    (serde $name:ident, $base_ty:ty) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<T: $crate::__private::serde::Serializer>(
+                &self,
+                serializer: T,
+            ) -> ::core::result::Result<T::Ok, T::Error> {
+                // Serialize the index, not the shifted value stored in the symbol.
+                <$base_ty as $crate::__private::serde::Serialize>::serialize(
+                    &(self.0.get() - 1),
+                    serializer,
+                )
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D: $crate::__private::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                let index = <$base_ty as $crate::__private::serde::Deserialize<'de>>::deserialize(
+                    deserializer,
+                )?;
+                // Indices above `usize::MAX` would be truncated by `to_usize`.
+                let symbol = <usize as ::core::convert::TryFrom<$base_ty>>::try_from(index)
+                    .ok()
+                    .and_then(<Self as $crate::Symbol>::try_from_usize);
+                let ::core::option::Option::Some(symbol) = symbol else {
+                    return ::core::result::Result::Err(
+                        <D::Error as $crate::__private::serde::de::Error>::custom(::core::concat!(
+                            "invalid index value for `",
+                            ::core::stringify!($name),
+                            "`"
+                        )),
+                    );
+                };
+                ::core::result::Result::Ok(symbol)
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "serde"))]
+macro_rules! __define_symbol_impl {
+    (serde $name:ident, $base_ty:ty) => {
+        ::core::compile_error!("`with serde` needs the `serde` feature of `string-hash-interner`");
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,12 +298,20 @@ mod tests {
         assert_eq!(size_of::<DefaultSymbol>(), size_of::<u32>());
     }
 
+    #[test]
+    fn u8_same_size_as_optional() {
+        assert_eq!(size_of::<SymbolU8>(), 1);
+        assert_eq!(size_of::<Option<SymbolU8>>(), 1);
+    }
+
     #[test]
     fn same_size_as_optional() {
         assert_eq!(
             size_of::<DefaultSymbol>(),
             size_of::<Option<DefaultSymbol>>()
         );
+        assert_eq!(size_of::<SymbolU16>(), size_of::<Option<SymbolU16>>());
+        assert_eq!(size_of::<SymbolUsize>(), size_of::<Option<SymbolUsize>>());
     }
 
     #[test]
@@ -163,9 +356,14 @@ mod tests {
                 );
                 assert_eq!(<$name>::try_from_usize(<$base_ty>::MAX as usize), None);
                 assert_eq!(<$name>::try_from_usize(<usize>::MAX), None);
+                assert_eq!(<$name>::MAX_INDEX, <$base_ty>::MAX as usize - 1);
             }
         };
     }
+    gen_test_for!(
+        try_from_usize_works_for_u8:
+        struct SymbolU8(NonZeroU8; u8);
+    );
     gen_test_for!(
         try_from_usize_works_for_u16:
         struct SymbolU16(NonZeroU16; u16);
@@ -174,8 +372,46 @@ mod tests {
         try_from_usize_works_for_u32:
         struct SymbolU32(NonZeroU32; u32);
     );
+    #[cfg(target_pointer_width = "64")]
+    gen_test_for!(
+        try_from_usize_works_for_u64:
+        struct SymbolU64(NonZeroU64; u64);
+    );
     gen_test_for!(
         try_from_usize_works_for_usize:
         struct SymbolUsize(NonZeroUsize; usize);
     );
+
+    #[test]
+    fn try_from_usize_works_for_u128() {
+        for val in [0, 1, 10, u32::MAX as usize, usize::MAX - 1, usize::MAX] {
+            assert_eq!(SymbolU128::try_from_usize(val).unwrap().to_usize(), val);
+        }
+        assert_eq!(size_of::<Option<SymbolU128>>(), size_of::<u128>());
+        assert_eq!(SymbolU128::MAX_INDEX, usize::MAX);
+        assert_eq!(<usize as Symbol>::MAX_INDEX, usize::MAX);
+    }
+
+    crate::define_symbol!(struct DefinedU8(u8));
+
+    #[test]
+    fn define_symbol_works() {
+        assert_eq!(size_of::<Option<DefinedU8>>(), 1);
+        for val in 0..u8::MAX as usize {
+            assert_eq!(DefinedU8::try_from_usize(val).unwrap().to_usize(), val);
+        }
+        assert_eq!(DefinedU8::try_from_usize(u8::MAX as usize), None);
+        assert_eq!(DefinedU8::try_from_usize(usize::MAX), None);
+        assert_eq!(DefinedU8::MAX_INDEX, SymbolU8::MAX_INDEX);
+    }
+
+    crate::define_symbol!(struct DefinedU128(u128));
+
+    #[test]
+    fn define_symbol_works_for_u128() {
+        for val in [0, 1, u32::MAX as usize, usize::MAX - 1, usize::MAX] {
+            assert_eq!(DefinedU128::try_from_usize(val).unwrap().to_usize(), val);
+        }
+        assert_eq!(DefinedU128::MAX_INDEX, usize::MAX);
+    }
 }
@@ -1,79 +1,1465 @@
-use std::hash::{BuildHasher, Hash, Hasher};
+use std::{
+    any::TypeId,
+    ffi::{CStr, CString},
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+use fxhash::{FxBuildHasher, FxHasher};
+use string_hash_interner::{
+    backend::{Backend, BucketBackend, HashedBackend, PrefixedBackend, StringBackend},
+    symbol::{SymbolU128, SymbolU16, SymbolU64, SymbolU8},
+    CaseInsensitiveInterner, ConcurrentInterner, DefaultHashBuilder,
+    DefaultStringInterner as StringInterner, DefaultSymbol, Entry, FixedHashBuilder, HashStats,
+    InternError, Interner, InternerBuilder, ReserveError, ResolveError, ResolveTable, Symbol,
+};
+
+fn expect_valid_symbol<S>(index: usize) -> S
+where
+    S: Symbol,
+{
+    S::try_from_usize(index).expect("encountered invalid symbol")
+}
+
+/// Calls the generic function `$test` once for every backend storing `str`,
+/// inferring the symbol type from its bounds.
+///
+/// With `hashed`, only the backends implementing [`HashedBackend`] are used.
+macro_rules! for_each_backend {
+    (hashed $test:ident) => {
+        $test::<StringBackend<str, _>>();
+        $test::<PrefixedBackend<str, _>>();
+        $test::<BucketBackend<str, _>>();
+    };
+    ($test:ident) => {
+        for_each_backend!(hashed $test);
+        $test::<StringBackend<str, _, usize, false>>();
+    };
+}
+
+#[test]
+fn new_works() {
+    let interner = StringInterner::new();
+    assert_eq!(interner.len(), 0);
+    assert!(interner.is_empty());
+    let other = StringInterner::new();
+    assert!(Iterator::eq(interner.iter(), other.iter()));
+}
+
+#[test]
+fn is_empty_works() {
+    let mut interner = StringInterner::new();
+    assert!(interner.is_empty());
+    interner.intern("aa");
+    assert!(!interner.is_empty());
+}
+
+#[test]
+fn clone_works() {
+    let mut interner = StringInterner::new();
+    assert_eq!(interner.intern("aa").to_usize(), 0);
+
+    let mut cloned = interner.clone();
+    assert!(Iterator::eq(interner.iter(), cloned.iter()));
+    // And the clone should have the same interned values
+    assert_eq!(cloned.intern("aa").to_usize(), 0);
+}
+
+#[test]
+fn intern_works() {
+    let mut interner = StringInterner::new();
+    // Insert 3 unique strings:
+    let aa = interner.intern("aa").to_usize();
+    let bb = interner.intern("bb").to_usize();
+    let cc = interner.intern("cc").to_usize();
+    // All symbols must be different from each other.
+    assert_ne!(aa, bb);
+    assert_ne!(bb, cc);
+    assert_ne!(cc, aa);
+    // The length of the string interner must be 3 at this point.
+    assert_eq!(interner.len(), 3);
+    // Insert the same 3 unique strings, yield the same symbols:
+    assert_eq!(
+        interner.resolve(<DefaultSymbol>::try_from_usize(aa).unwrap()),
+        Some("aa")
+    );
+    assert_eq!(
+        interner.intern("aa").to_usize(),
+        aa,
+        "'aa' did not produce the same symbol",
+    );
+    assert_eq!(
+        interner.intern("bb").to_usize(),
+        bb,
+        "'bb' did not produce the same symbol",
+    );
+    assert_eq!(
+        interner.intern("cc").to_usize(),
+        cc,
+        "'cc' did not produce the same symbol",
+    );
+    assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn next_symbol_works() {
+    let mut interner = StringInterner::new();
+    for s in ["aa", "bb", "cc"] {
+        let next = interner.next_symbol();
+        assert_eq!(next, Some(interner.intern(s)));
+    }
+    let next = interner.next_symbol();
+    interner.intern("aa");
+    assert_eq!(interner.next_symbol(), next);
+
+    let mut interner = string_hash_interner::StringInterner::<SymbolU16>::new();
+    for n in 0..u16::MAX {
+        interner.intern(n.to_string());
+    }
+    assert_eq!(interner.next_symbol(), None);
+}
+
+#[test]
+fn builder_works() {
+    let words = ["aa", "bb", "aa", "", "cc", "bb", ""];
+    let mut builder =
+        InternerBuilder::<str, DefaultSymbol, _>::with_hasher(FixedHashBuilder::with_seed(42));
+    for (index, word) in words.iter().enumerate() {
+        assert_eq!(builder.push(word), index);
+    }
+    assert_eq!(builder.len(), words.len());
+    let (interner, symbols) = builder.build_deduplicated();
+
+    let mut expected = string_hash_interner::StringInterner::<DefaultSymbol, _>::with_seed(42);
+    let expected_symbols = words.map(|word| expected.intern(word));
+    assert_eq!(symbols, expected_symbols);
+    assert_eq!(interner.len(), 4);
+    for (symbol, word) in symbols.iter().zip(words) {
+        assert_eq!(interner.resolve(*symbol), Some(word));
+        assert_eq!(interner.get(word), Some(*symbol));
+        assert_eq!(interner.get_hash(*symbol), expected.get_hash(*symbol));
+    }
+
+    let (interner, symbols): (StringInterner, _) =
+        InternerBuilder::<str>::new().build_deduplicated();
+    assert!(interner.is_empty());
+    assert!(symbols.is_empty());
+}
+
+#[test]
+fn try_intern_works() {
+    fn test_for_backend<B: Backend<str, SymbolU16>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<SymbolU16, DefaultHashBuilder, B>::new();
+        for n in 0..u16::MAX {
+            interner.intern(n.to_string());
+        }
+        let len = interner.len();
+        let expected = interner
+            .iter()
+            .map(|(_, s)| s.to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            interner.try_intern("overflow"),
+            Err(InternError::SymbolOverflow)
+        );
+        assert!(interner.try_intern_and_hash("overflow").is_err());
+        assert_eq!(interner.len(), len);
+        assert_eq!(interner.get("overflow"), None);
+        let actual = interner
+            .iter()
+            .map(|(_, s)| s.to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+
+        // Already interned strings are still resolved.
+        let symbol = interner.get("42").unwrap();
+        assert_eq!(interner.try_intern("42"), Ok(symbol));
+    }
+    for_each_backend!(test_for_backend);
+}
+
+#[test]
+fn offset_overflow_errors() {
+    let mut interner = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        StringBackend<str, DefaultSymbol, u16>,
+    >::new();
+    let long = "a".repeat(usize::from(u16::MAX) - 1);
+    let a = interner.intern(long.as_str());
+    let b = interner.intern("b");
+
+    assert_eq!(interner.try_intern("c"), Err(InternError::OffsetOverflow));
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.get("c"), None);
+    // Empty strings still fit.
+    let empty = interner.intern("");
+
+    assert_eq!(interner.resolve(a), Some(long.as_str()));
+    assert_eq!(interner.resolve(b), Some("b"));
+    assert_eq!(interner.resolve(empty), Some(""));
+    assert_eq!(interner.resolve_table().resolve(b), Some("b"));
+    let strings = interner.iter_from(b).map(|(_, s)| s).collect::<Vec<_>>();
+    assert_eq!(strings, ["b", ""]);
+}
+
+#[test]
+fn symbol_u8_works() {
+    let mut interner = string_hash_interner::StringInterner::<SymbolU8>::new();
+    for n in 0..u8::MAX {
+        let symbol = interner.intern(n.to_string());
+        assert_eq!(symbol.to_usize(), n as usize);
+    }
+    assert_eq!(
+        interner.try_intern("overflow"),
+        Err(InternError::SymbolOverflow)
+    );
+    assert_eq!(interner.resolve(interner.get("254").unwrap()), Some("254"));
+}
+
+#[test]
+fn wide_symbols_work() {
+    let mut interner = string_hash_interner::StringInterner::<SymbolU64>::new();
+    let aa = interner.intern("aa");
+    assert_eq!(interner.intern("bb").to_usize(), 1);
+    assert_eq!(interner.resolve(aa), Some("aa"));
+
+    let mut interner = string_hash_interner::StringInterner::<SymbolU128>::new();
+    let aa = interner.intern("aa");
+    assert_eq!(interner.intern("bb").to_usize(), 1);
+    assert_eq!(interner.resolve(aa), Some("aa"));
+}
+
+#[test]
+fn intern_fmt_works() {
+    use string_hash_interner::intern_fmt;
+
+    struct Panics;
+    impl std::fmt::Display for Panics {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("partial")?;
+            panic!("formatting failed");
+        }
+    }
+
+    let mut interner = StringInterner::new();
+    let aa = interner.intern("main.rs:1");
+    assert_eq!(intern_fmt!(interner, "{}:{}", "main.rs", 1), aa);
+    assert_eq!(interner.buffer().len(), "main.rs:1".len());
+    let bb = intern_fmt!(interner, "{}:{}", "lib.rs", 22);
+    assert_eq!(interner.resolve(bb), Some("lib.rs:22"));
+    assert_eq!(interner.get("lib.rs:22"), Some(bb));
+    assert_eq!(
+        interner.intern_fmt(format_args!("literal")),
+        interner.intern("literal")
+    );
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        interner.intern_fmt(format_args!("{}", Panics))
+    }));
+    assert!(result.is_err());
+    let cc = interner.intern("cc");
+    assert_eq!(interner.resolve(cc), Some("cc"));
+    assert_eq!(interner.len(), 4);
+
+    let mut interner = string_hash_interner::StringInterner::<SymbolU8>::new();
+    for n in 0..u8::MAX {
+        intern_fmt!(interner, "{n}");
+    }
+    let len = interner.buffer().len();
+    assert_eq!(
+        interner.try_intern_fmt(format_args!("{}", 1000)),
+        Err(InternError::SymbolOverflow)
+    );
+    assert_eq!(interner.buffer().len(), len);
+    assert_eq!(intern_fmt!(interner, "{}", 7), interner.get("7").unwrap());
+}
+
+#[test]
+fn intern_chars_works() {
+    let mut interner = StringInterner::new();
+    let aa = interner.intern("aä");
+    assert_eq!(interner.intern_chars(['a', 'ä']), aa);
+    assert_eq!(interner.buffer().len(), "aä".len());
+    let bb = interner.intern_chars("b€🦀".chars());
+    assert_eq!(interner.resolve(bb), Some("b€🦀"));
+    assert_eq!(interner.get("b€🦀"), Some(bb));
+    let empty = interner.intern_chars([]);
+    assert_eq!(interner.resolve(empty), Some(""));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        interner.intern_chars("cc".chars().chain(std::iter::from_fn(|| panic!())))
+    }));
+    assert!(result.is_err());
+    let cc = interner.intern("dd");
+    assert_eq!(interner.resolve(cc), Some("dd"));
+    assert_eq!(interner.len(), 4);
+
+    let mut interner = string_hash_interner::StringInterner::<SymbolU8>::new();
+    for n in 0..u8::MAX {
+        interner.intern(n.to_string());
+    }
+    let len = interner.buffer().len();
+    assert_eq!(
+        interner.try_intern_chars("1000".chars()),
+        Err(InternError::SymbolOverflow)
+    );
+    assert_eq!(interner.buffer().len(), len);
+    assert_eq!(interner.intern_chars(['7']), interner.get("7").unwrap());
+}
+
+#[test]
+fn intern_chunks_works() {
+    let mut interner = Interner::<[u8]>::new();
+    let abc = interner.intern(b"abc");
+    let abc_hash = interner.get_hash(abc);
+    assert_eq!(interner.intern_chunks([&b"a"[..], b"", b"bc"]), abc);
+    assert_eq!(interner.buffer().len(), 3);
+    let de = interner.intern_chunks([&b"d"[..], b"e"]);
+    assert_eq!(interner.resolve(de), Some(&b"de"[..]));
+    assert_eq!(interner.get(b"de"), Some(de));
+    assert_eq!(interner.get_hash(de), Some(interner.hash_of(b"de")));
+    assert_eq!(interner.get_hash(abc), abc_hash);
+    let empty = interner.intern_chunks([]);
+    assert_eq!(interner.resolve(empty), Some(&b""[..]));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        interner.intern_chunks(
+            [&b"ff"[..]]
+                .into_iter()
+                .chain(std::iter::from_fn(|| panic!())),
+        )
+    }));
+    assert!(result.is_err());
+    let gg = interner.intern(b"gg");
+    assert_eq!(interner.resolve(gg), Some(&b"gg"[..]));
+    assert_eq!(interner.len(), 4);
+
+    let mut interner = string_hash_interner::Interner::<[u8], SymbolU8>::new();
+    for n in 0..u8::MAX {
+        interner.intern(n.to_string());
+    }
+    let len = interner.buffer().len();
+    assert_eq!(
+        interner.try_intern_chunks([&b"10"[..], b"00"]),
+        Err(InternError::SymbolOverflow)
+    );
+    assert_eq!(interner.buffer().len(), len);
+    assert_eq!(
+        interner.intern_chunks([&b"7"[..]]),
+        interner.get(b"7").unwrap()
+    );
+}
+
+#[test]
+fn hash_of_works() {
+    let mut interner = StringInterner::new();
+    let hash = interner.hash_of("aa");
+    assert_eq!(hash, interner.hasher().hash_one("aa"));
+    assert_eq!(interner.get_with_hash("aa", hash), None);
+    let aa = interner.intern("aa");
+    assert_eq!(interner.get_hash(aa), Some(hash));
+    assert_eq!(interner.get_with_hash("aa", hash), Some(aa));
+    assert_ne!(interner.hash_of("bb"), hash);
+}
+
+#[test]
+#[should_panic]
+fn intern_overflow_panics() {
+    let mut interner = string_hash_interner::StringInterner::<SymbolU16>::new();
+    for n in 0..=u16::MAX {
+        interner.intern(n.to_string());
+    }
+}
+
+#[test]
+fn bucket_backend_keeps_addresses() {
+    let mut interner = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        BucketBackend<str, DefaultSymbol>,
+    >::new();
+    let aa = interner.intern("aa");
+    let ptr: *const str = interner.resolve(aa).unwrap();
+    // Enough strings to reallocate a single buffer many times.
+    for n in 0..10_000 {
+        interner.intern(n.to_string());
+    }
+    interner.intern("x".repeat(100_000));
+    interner.shrink_to_fit();
+    assert_eq!(interner.resolve(aa).unwrap() as *const str, ptr);
+    // SAFETY: The bucket backend never moves the interned strings.
+    assert_eq!(unsafe { &*ptr }, "aa");
+    assert_eq!(
+        interner.resolve(interner.get("9999").unwrap()),
+        Some("9999")
+    );
+}
+
+#[test]
+fn uncached_hashes_work() {
+    let mut interner = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        StringBackend<str, DefaultSymbol, usize, false>,
+    >::new();
+    // Grow the deduplication map a few times, which rehashes the strings.
+    let symbols = (0..1000)
+        .map(|n| interner.intern(n.to_string()))
+        .collect::<Vec<_>>();
+    for (n, &symbol) in symbols.iter().enumerate() {
+        assert_eq!(interner.get(n.to_string()), Some(symbol));
+        assert_eq!(interner.resolve(symbol), Some(n.to_string().as_str()));
+    }
+    interner.shrink_to_fit();
+    assert_eq!(interner.get("999"), Some(symbols[999]));
+
+    // Merging recomputes the hashes of the uncached strings.
+    let mut cached = StringInterner::new();
+    cached.intern("0");
+    let merged = cached.merge(&interner);
+    assert_eq!(cached.len(), 1000);
+    assert_eq!(cached.get("999"), Some(merged[999]));
+    assert_eq!(
+        cached.get_hash(merged[999]),
+        Some(cached.hasher().hash_one("999"))
+    );
+}
+
+#[test]
+fn case_insensitive_works() {
+    let mut interner = <CaseInsensitiveInterner>::new();
+    let first = interner.intern("Content-Type");
+    let second = interner.intern("content-type");
+    let third = interner.intern(String::from("CONTENT-TYPE"));
+    assert_eq!(first, second);
+    assert_eq!(first, third);
+    assert_eq!(interner.len(), 1);
+    assert_eq!(interner.resolve(second).unwrap().as_str(), "Content-Type");
+    assert_eq!(interner.get("cOnTeNt-TyPe"), Some(first));
+
+    // Only ASCII case is ignored.
+    let other = interner.intern("Content-Typé");
+    assert_ne!(other, first);
+    assert_ne!(interner.intern("CONTENT-TYPÉ"), other);
+    // Prefixes are still different strings.
+    assert_ne!(interner.intern("content"), first);
+
+    // Long strings are hashed in chunks.
+    let long = "Ab".repeat(100);
+    let long_symbol = interner.intern(long.as_str());
+    assert_eq!(interner.get(long.to_lowercase()), Some(long_symbol));
+    assert_eq!(interner.resolve(long_symbol).unwrap().as_str(), long);
+}
+
+#[test]
+fn rehash_with_works() {
+    fn test_for_backend<B: HashedBackend<str, DefaultSymbol>>() {
+        let strings = ["aa", "bb", "", "cc"];
+        let interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::from_iter(
+                strings,
+            );
+        let symbols = strings.map(|s| interner.get(s).unwrap());
+
+        let hasher = FixedHashBuilder::with_seed(42);
+        let mut interner = interner.rehash_with(hasher);
+        for (string, symbol) in strings.into_iter().zip(symbols) {
+            assert_eq!(interner.get(string), Some(symbol));
+            assert_eq!(interner.resolve(symbol), Some(string));
+            assert_eq!(interner.get_hash(symbol), Some(hasher.hash_one(string)));
+        }
+        assert_eq!(interner.intern("aa"), symbols[0]);
+        assert_eq!(interner.len(), 4);
+    }
+    for_each_backend!(hashed test_for_backend);
+}
+
+#[test]
+fn clear_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        interner.intern("aa");
+        interner.intern("bb");
+        interner.clear();
+        assert_eq!(interner.len(), 0);
+        assert!(interner.is_empty());
+        assert_eq!(interner.get("aa"), None);
+        assert_eq!(interner.iter().next(), None);
+
+        let cc = interner.intern("cc");
+        assert_eq!(cc.to_usize(), 0);
+        assert_eq!(interner.resolve(cc), Some("cc"));
+        assert_eq!(interner.len(), 1);
+    }
+    for_each_backend!(test_for_backend);
+}
+
+#[test]
+fn drain_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let aa = interner.intern("aa");
+        let bb = interner.intern_static("bb");
+        let capacity = interner.capacity();
+
+        let mut drain = interner.drain();
+        assert_eq!(drain.len(), 2);
+        assert_eq!(drain.next(), Some((aa, String::from("aa"))));
+        assert_eq!(drain.next(), Some((bb, String::from("bb"))));
+        assert_eq!(drain.next(), None);
+        drop(drain);
+        assert!(interner.is_empty());
+        assert_eq!(interner.get("aa"), None);
+        assert_eq!(interner.capacity(), capacity);
+
+        // Dropping the iterator early still clears the interner.
+        interner.extend(["cc", "dd"]);
+        assert_eq!(interner.drain().next(), Some((aa, String::from("cc"))));
+        assert!(interner.is_empty());
+        assert_eq!(interner.iter().next(), None);
+        assert_eq!(interner.intern("dd").to_usize(), 0);
+
+        // Leaking the iterator keeps the strings.
+        core::mem::forget(interner.drain());
+        assert_eq!(interner.get("dd"), Some(aa));
+        assert_eq!(interner.len(), 1);
+    }
+    for_each_backend!(test_for_backend);
+}
+
+#[test]
+fn truncate_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let aa = interner.intern("aa");
+        let bb = interner.intern_static("bb");
+        interner.intern("cc");
+        interner.intern_static("dd");
+        let long = "e".repeat(5000);
+        interner.intern(&long);
+        interner.truncate(10);
+        assert_eq!(interner.len(), 5);
+
+        interner.truncate(2);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("cc"), None);
+        assert_eq!(interner.get(&long), None);
+        assert!(interner.iter().eq([(aa, "aa"), (bb, "bb")]));
+
+        let long_again = interner.intern(&long);
+        assert_eq!(long_again.to_usize(), 2);
+        assert_eq!(interner.resolve(long_again), Some(&*long));
+        assert_eq!(interner.intern("dd").to_usize(), 3);
+        assert_eq!(interner.intern("aa"), aa);
+
+        assert_eq!(interner.pop(), Some(expect_valid_symbol(3)));
+        assert_eq!(interner.get("dd"), None);
+        interner.truncate(0);
+        assert!(interner.is_empty());
+        assert_eq!(interner.get("aa"), None);
+        assert_eq!(interner.pop(), None);
+        assert_eq!(interner.intern("bb").to_usize(), 0);
+    }
+    for_each_backend!(test_for_backend);
+}
+
+#[test]
+fn retain_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let symbols = interner.intern_all(["aa", "b", "cc", "d", "ee"]);
+        let remap = interner.retain(|symbol, string| {
+            assert_eq!(symbols[symbol.to_usize()], symbol);
+            string.len() == 2
+        });
+        assert_eq!(remap.len(), 5);
+        assert_eq!(remap[1], None);
+        assert_eq!(remap[3], None);
+        assert!(interner.iter().eq([
+            (expect_valid_symbol(0), "aa"),
+            (expect_valid_symbol(1), "cc"),
+            (expect_valid_symbol(2), "ee"),
+        ]));
+        for (old, string) in [(0, "aa"), (2, "cc"), (4, "ee")] {
+            assert_eq!(remap[old], interner.get(string));
+        }
+        assert_eq!(interner.get("b"), None);
+        assert_eq!(interner.intern("b").to_usize(), 3);
+        assert_eq!(interner.intern("ee"), remap[4].unwrap());
+
+        assert!(interner.retain(|_, _| false).iter().all(Option::is_none));
+        assert!(interner.is_empty());
+        assert_eq!(interner.get("aa"), None);
+    }
+    for_each_backend!(test_for_backend);
+
+    static LONG: &str = "a string that is long enough to be worth not copying";
+    let mut interner = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        BucketBackend<str, DefaultSymbol>,
+    >::new();
+    interner.intern("aa");
+    interner.intern_static(LONG);
+    interner.intern("bb");
+    let remap = interner.retain(|_, string| string != "aa");
+    let long = remap[1].unwrap();
+    assert!(std::ptr::eq(interner.resolve(long).unwrap(), LONG));
+    assert_eq!(interner.get(LONG), Some(long));
+    assert_eq!(interner.resolve(remap[2].unwrap()), Some("bb"));
+}
+
+#[test]
+fn retain_compacts_in_place() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol> + 'static>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let strings: Vec<String> = (0..1000).map(|n| format!("string {n}")).collect();
+        interner.intern_all(&strings);
+        let buffer_capacity = interner.buffer_capacity();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            interner.retain(|symbol, _| {
+                assert!(symbol.to_usize() < 500);
+                false
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(interner.len(), 1000);
+        assert_eq!(interner.get("string 999").map(|s| s.to_usize()), Some(999));
+
+        interner.retain(|symbol, _| symbol.to_usize() % 3 == 0);
+        // The bucket backend interns the kept strings into new buckets.
+        if TypeId::of::<B>() != TypeId::of::<BucketBackend<str, DefaultSymbol>>() {
+            assert_eq!(interner.buffer_capacity(), buffer_capacity);
+        }
+        assert!(interner
+            .strings()
+            .eq(strings.iter().step_by(3).map(String::as_str)));
+        for (index, string) in strings.iter().enumerate().step_by(3) {
+            assert_eq!(interner.get(string).map(|s| s.to_usize()), Some(index / 3));
+        }
+    }
+    for_each_backend!(test_for_backend);
+}
+
+#[test]
+fn checkpoint_works() {
+    let mut interner = StringInterner::new();
+    let aa = interner.intern("aa");
+    let checkpoint = interner.checkpoint();
+    assert_eq!(checkpoint.count(), 1);
+    interner.intern("bb");
+    let inner = interner.checkpoint();
+    interner.intern("cc");
+    interner.rollback(inner);
+    assert_eq!(interner.get("cc"), None);
+    assert_eq!(interner.len(), 2);
+    interner.rollback(checkpoint);
+    assert!(interner.iter().eq([(aa, "aa")]));
+    interner.rollback(checkpoint);
+    assert_eq!(interner.len(), 1);
+    assert_eq!(interner.intern("cc").to_usize(), 1);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "checkpoint used after strings were removed")]
+fn rollback_after_clear_panics() {
+    let mut interner = StringInterner::new();
+    interner.intern("aa");
+    let checkpoint = interner.checkpoint();
+    interner.clear();
+    interner.rollback(checkpoint);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "checkpoint used after strings were removed")]
+fn rollback_after_clear_and_intern_panics() {
+    let mut interner = StringInterner::new();
+    interner.intern("aa");
+    let checkpoint = interner.checkpoint();
+    interner.clear();
+    interner.intern("bb");
+    interner.intern("cc");
+    interner.rollback(checkpoint);
+}
+
+#[test]
+fn debug_works() {
+    let mut interner = StringInterner::new();
+    assert_eq!(format!("{interner:?}"), "{}");
+    interner.intern("Elephant");
+    interner.intern("Tiger");
+    assert_eq!(format!("{interner:?}"), r#"{0: "Elephant", 1: "Tiger"}"#);
+    assert_eq!(
+        format!("{:?}", interner.freeze()),
+        r#"FrozenInterner({0: "Elephant", 1: "Tiger"})"#
+    );
+}
+
+#[test]
+fn concurrent_interner_works() {
+    let interner = ConcurrentInterner::<str>::with_shards(6);
+    assert_eq!(interner.shards(), 8);
+    let strings: Vec<String> = (0..1000).map(|n| n.to_string()).collect();
+    let symbols: Vec<Vec<DefaultSymbol>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|offset| {
+                let (interner, strings) = (&interner, &strings);
+                scope.spawn(move || {
+                    let mut symbols = vec![None; strings.len()];
+                    for n in 0..strings.len() {
+                        let n = (n + offset * 125) % strings.len();
+                        symbols[n] = Some(interner.intern(&strings[n]));
+                    }
+                    symbols.into_iter().map(Option::unwrap).collect()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert!(symbols.iter().all(|s| *s == symbols[0]));
+    assert_eq!(interner.len(), 1000);
+    for (string, &symbol) in strings.iter().zip(&symbols[0]) {
+        assert_eq!(interner.get(string), Some(symbol));
+        assert_eq!(interner.resolve(symbol), Some(&**string));
+    }
+    assert_eq!(interner.get("missing"), None);
+    assert!(interner.contains("999"));
+
+    let mut interner = ConcurrentInterner::<str, SymbolU8>::with_shards(1);
+    for n in 0..u8::MAX {
+        interner.intern(n.to_string());
+    }
+    assert_eq!(
+        interner.try_intern("overflow"),
+        Err(InternError::SymbolOverflow)
+    );
+    assert_eq!(interner.len(), 255);
+    interner.clear();
+    assert!(interner.is_empty());
+    assert_eq!(interner.resolve(expect_valid_symbol(0)), None);
+}
+
+#[test]
+fn string_arguments_work() {
+    use std::{borrow::Cow, rc::Rc};
+
+    let mut interner = StringInterner::new();
+    let symbol = interner.intern("abcd");
+    let owned = String::from("abcd");
+    assert_eq!(interner.get(&owned), Some(symbol));
+    assert_eq!(interner.get(owned.clone()), Some(symbol));
+    assert_eq!(interner.get(Box::<str>::from("abcd")), Some(symbol));
+    assert_eq!(interner.get(Cow::Borrowed("abcd")), Some(symbol));
+    assert_eq!(interner.get(Rc::<str>::from("abcd")), Some(symbol));
+
+    let mut interner = <Interner<[u8]>>::new();
+    let symbol = interner.intern(b"abcd");
+    let array: &[u8; 4] = b"abcd";
+    let vec = array.to_vec();
+    assert_eq!(interner.get(array), Some(symbol));
+    assert_eq!(interner.get(*array), Some(symbol));
+    assert_eq!(interner.get(&vec), Some(symbol));
+    assert_eq!(interner.get(&vec[..]), Some(symbol));
+    assert_eq!(interner.get(vec), Some(symbol));
+    assert_eq!(interner.get("abcd"), Some(symbol));
+    assert_eq!(interner.intern([b'a', b'b', b'c', b'd']), symbol);
+    assert_eq!(interner.get(b"abc"), None);
+}
+
+string_hash_interner::global_interner!(names: str);
+string_hash_interner::global_interner!(c_names: CStr);
+
+#[test]
+fn global_interner_works() {
+    let symbols: Vec<DefaultSymbol> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| names::intern("shared")))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert!(symbols.iter().all(|&symbol| symbol == symbols[0]));
+    let shared: &'static str = names::resolve(symbols[0]).unwrap();
+    assert_eq!(shared, "shared");
+    assert_eq!(names::get("shared"), Some(symbols[0]));
+    assert_eq!(names::get("missing"), None);
+    assert!(names::interner().contains("shared"));
+
+    let symbol = c_names::intern(c"shared");
+    assert_eq!(c_names::resolve(symbol), Some(c"shared"));
+    assert_eq!(c_names::interner().len(), 1);
+}
+
+#[test]
+fn read_write_works() {
+    fn round_trip<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let long = "x".repeat(300);
+        let symbols: Vec<_> = ["", "a", "ü", &long]
+            .into_iter()
+            .map(|string| interner.intern(string))
+            .collect();
+        let mut bytes = Vec::new();
+        interner.write_to(&mut bytes).unwrap();
+        assert_eq!(bytes[..8], *b"\x04\x00\x01a\x02\xc3\xbc\xac");
+        assert_eq!(bytes.len(), 8 + 1 + 300);
+
+        let restored =
+            StringInterner::read_from(&bytes[..], DefaultHashBuilder::default()).unwrap();
+        assert_eq!(restored.len(), 4);
+        for (symbol, string) in symbols.into_iter().zip(["", "a", "ü", &long]) {
+            assert_eq!(restored.resolve(symbol), Some(string));
+            assert_eq!(restored.get(string), Some(symbol));
+        }
+    }
+    round_trip::<StringBackend<str, DefaultSymbol>>();
+    round_trip::<BucketBackend<str, DefaultSymbol>>();
+    round_trip::<PrefixedBackend<str, DefaultSymbol>>();
+
+    let mut bytes = Vec::new();
+    Interner::<CStr>::from_iter([c"a"])
+        .write_to(&mut bytes)
+        .unwrap();
+    assert_eq!(bytes, b"\x01\x02a\x00");
+    let restored = Interner::<CStr>::read_from(&bytes[..], DefaultHashBuilder::default()).unwrap();
+    assert_eq!(
+        restored.get(c"a"),
+        Some(DefaultSymbol::try_from_usize(0).unwrap())
+    );
+
+    let read = |bytes: &[u8]| StringInterner::read_from(bytes, DefaultHashBuilder::default());
+    let restored = read(b"\x03\x01a\x01b\x01a").unwrap();
+    assert_eq!(restored.len(), 2);
+    let error = |bytes: &[u8]| read(bytes).unwrap_err().kind();
+    assert_eq!(error(b"\x02\x01a"), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(error(b"\x01\x03ab"), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(error(b"\x01\x01\xff"), std::io::ErrorKind::InvalidData);
+    assert_eq!(error(&[0xff; 11]), std::io::ErrorKind::InvalidData);
+
+    let mut bytes = vec![0x81, 0x02];
+    for n in 0..257u16 {
+        let string = n.to_string();
+        bytes.push(string.len() as u8);
+        bytes.extend_from_slice(string.as_bytes());
+    }
+    let error = Interner::<str, SymbolU8>::read_from(&bytes[..], DefaultHashBuilder::default())
+        .unwrap_err();
+    assert_eq!(
+        error.into_inner().unwrap().downcast_ref(),
+        Some(&InternError::SymbolOverflow)
+    );
+}
+
+#[test]
+fn capacity_works() {
+    let interner = StringInterner::with_capacity(100);
+    assert!(interner.capacity() >= 100);
+    assert!(interner.buffer_capacity() >= 100);
+
+    let mut interner = StringInterner::new();
+    assert_eq!(interner.capacity(), 0);
+    assert_eq!(interner.buffer_capacity(), 0);
+    interner.intern("aa");
+    assert!(interner.capacity() >= 1);
+    assert!(interner.buffer_capacity() >= 2);
+
+    let capacity = interner.capacity();
+    let buffer_capacity = interner.buffer_capacity();
+    interner.clear();
+    assert_eq!(interner.capacity(), capacity);
+    assert_eq!(interner.buffer_capacity(), buffer_capacity);
+}
+
+#[test]
+fn const_constructors_work() {
+    static INTERNER: std::sync::Mutex<Interner<str, DefaultSymbol, FixedHashBuilder>> =
+        std::sync::Mutex::new(Interner::const_with_seed(7));
+    const EMPTY: Interner<
+        str,
+        SymbolU16,
+        FixedHashBuilder,
+        StringBackend<str, SymbolU16, u16, false>,
+    > = Interner::const_with_hasher(FixedHashBuilder::with_seed(7));
+
+    let mut interner = INTERNER.lock().unwrap();
+    assert!(interner.is_empty());
+    assert_eq!(interner.capacity(), 0);
+    let aa = interner.intern("aa");
+    assert_eq!(interner.resolve(aa), Some("aa"));
+    assert_eq!(
+        interner.hash_of("aa"),
+        Interner::<str, DefaultSymbol, FixedHashBuilder>::with_seed(7).hash_of("aa")
+    );
+
+    let mut other = EMPTY;
+    assert_eq!(other.buffer_capacity(), 0);
+    let aa = other.intern("aa");
+    assert_eq!(other.get("aa"), Some(aa));
+}
+
+#[test]
+fn with_capacities_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner = string_hash_interner::StringInterner::<
+            DefaultSymbol,
+            DefaultHashBuilder,
+            B,
+        >::with_capacities(10, 600);
+        assert!(interner.capacity() >= 10);
+        assert!(interner.buffer_capacity() >= 600);
+        let buffer_capacity = interner.buffer_capacity();
+        for n in 0..10 {
+            interner.intern(format!("{n:059}"));
+        }
+        assert_eq!(interner.buffer_capacity(), buffer_capacity);
+
+        let interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::with_capacities(
+                0, 0,
+            );
+        assert_eq!(interner.buffer_capacity(), 0);
+    }
+    for_each_backend!(test_for_backend);
+}
+
+#[test]
+fn dedup_capacity_works() {
+    let mut interner = StringInterner::new();
+    assert_eq!(interner.dedup_capacity(), 0);
+    interner.intern("aa");
+    assert!(interner.dedup_capacity() >= 1);
+
+    interner.reserve(1000);
+    let capacity = interner.dedup_capacity();
+    assert!(capacity >= 1001);
+    assert!(interner.capacity() <= capacity);
+    // Interning up to the capacity doesn't grow the map.
+    let spare = capacity - interner.len();
+    interner.extend((0..spare).map(|n| n.to_string()));
+    assert_eq!(interner.dedup_capacity(), capacity);
+    interner.intern("bb");
+    assert!(interner.dedup_capacity() > capacity);
+
+    interner.clear();
+    interner.shrink_to_fit();
+    assert_eq!(interner.dedup_capacity(), 0);
+}
+
+#[test]
+fn hash_stats_works() {
+    /// Hashes strings by their length, so strings of the same length collide.
+    #[derive(Default)]
+    struct LenHasher(u64);
+
+    impl Hasher for LenHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            self.0 += bytes.len() as u64;
+        }
+    }
+
+    type LenBuildHasher = std::hash::BuildHasherDefault<LenHasher>;
+
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, LenBuildHasher, B>::new();
+        assert_eq!(
+            interner.hash_stats(),
+            HashStats {
+                distinct_hashes: 0,
+                collisions: 0,
+                max_chain_len: 0
+            }
+        );
+        interner.intern_all(["a", "b", "c", "dd", "ee", "fff"]);
+        assert_eq!(
+            interner.hash_stats(),
+            HashStats {
+                distinct_hashes: 3,
+                collisions: 5,
+                max_chain_len: 3
+            }
+        );
+    }
+    for_each_backend!(test_for_backend);
+}
+
+#[test]
+fn memory_usage_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        assert_eq!(interner.memory_usage().total(), 0);
+
+        for n in 0..100 {
+            interner.intern(n.to_string());
+        }
+        let usage = interner.memory_usage();
+        assert!(usage.buffer_bytes >= interner.buffer_capacity());
+        assert!(usage.ends_bytes >= interner.len());
+        assert!(usage.dedup_bytes >= interner.len() * core::mem::size_of::<DefaultSymbol>());
+        assert_eq!(
+            usage.total(),
+            usage.buffer_bytes + usage.ends_bytes + usage.dedup_bytes
+        );
+
+        interner.clear();
+        assert_eq!(interner.memory_usage(), usage);
+    }
+    for_each_backend!(test_for_backend);
+
+    let mut wide = StringInterner::new();
+    let mut narrow = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        StringBackend<str, DefaultSymbol, u32>,
+    >::new();
+    let mut uncached = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        StringBackend<str, DefaultSymbol, usize, false>,
+    >::new();
+    for n in 0..100 {
+        wide.intern(n.to_string());
+        narrow.intern(n.to_string());
+        uncached.intern(n.to_string());
+    }
+    assert!(narrow.memory_usage().ends_bytes < wide.memory_usage().ends_bytes);
+    assert!(uncached.memory_usage().ends_bytes < wide.memory_usage().ends_bytes);
+}
+
+#[test]
+fn clone_from_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol> + Clone>() {
+        type Interner<B> =
+            string_hash_interner::StringInterner<DefaultSymbol, DefaultHashBuilder, B>;
+
+        let master = ["aa", "bb", "", "cc"].iter().collect::<Interner<B>>();
+        let mut local = (0..1000).map(|n| n.to_string()).collect::<Interner<B>>();
+        let buffer_capacity = local.buffer_capacity();
+        local.clone_from(&master);
+        assert!(local.eq_ordered(&master));
+        assert_eq!(local.get("bb"), master.get("bb"));
+        assert_eq!(local.get("1"), None);
+        assert_eq!(local.buffer_capacity(), buffer_capacity);
+
+        let mut empty = Interner::<B>::new();
+        empty.clone_from(&master);
+        assert!(empty.eq_ordered(&master));
+    }
+    for_each_backend!(test_for_backend);
+
+    let master = ["aa", "bb"]
+        .iter()
+        .collect::<string_hash_interner::StringInterner<
+            _,
+            DefaultHashBuilder,
+            BucketBackend<str, DefaultSymbol>,
+        >>();
+    let mut local = ["cc"]
+        .iter()
+        .collect::<string_hash_interner::StringInterner<_, _, _>>();
+    local.clone_from(&master);
+    assert!(local.eq_ordered(&master));
+}
+
+#[test]
+fn shrink_to_fit_releases_capacity() {
+    let mut interner = StringInterner::with_capacity(100_000);
+    for n in 0..10_000 {
+        interner.intern(n.to_string());
+    }
+    let capacity = interner.capacity();
+    let buffer_capacity = interner.buffer_capacity();
+    interner.shrink_to_fit();
+    assert!(interner.capacity() < capacity);
+    assert!(interner.capacity() >= 10_000);
+    assert!(interner.buffer_capacity() < buffer_capacity);
+    for n in 0..10_000 {
+        let s = n.to_string();
+        assert_eq!(interner.resolve(interner.get(&s).unwrap()), Some(&*s));
+    }
+}
+
+#[test]
+fn shrink_to_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner = string_hash_interner::StringInterner::<
+            DefaultSymbol,
+            DefaultHashBuilder,
+            B,
+        >::with_capacities(100_000, 1_000_000);
+        for n in 0..1000 {
+            interner.intern(n.to_string());
+        }
+        let capacity = interner.capacity();
+        let buffer_capacity = interner.buffer_capacity();
+
+        interner.shrink_to(10_000, 100_000);
+        assert!(interner.capacity() < capacity);
+        assert!(interner.capacity() >= 10_000);
+        assert!(interner.buffer_capacity() >= 100_000);
+
+        // Never shrinks below the interned strings, nor grows.
+        interner.shrink_to(0, 0);
+        assert!(interner.capacity() >= 1000);
+        interner.shrink_to(20_000, 2_000_000);
+        assert!(interner.capacity() < 20_000);
+        assert!(interner.buffer_capacity() <= buffer_capacity);
+
+        for n in 0..1000 {
+            let s = n.to_string();
+            assert_eq!(interner.resolve(interner.get(&s).unwrap()), Some(&*s));
+        }
+    }
+
+    for_each_backend!(test_for_backend);
+
+    let mut interner = StringInterner::with_capacities(100_000, 1_000_000);
+    interner.intern("hello");
+    interner.shrink_to(0, 100_000);
+    assert!((100_000..1_000_000).contains(&interner.buffer_capacity()));
+}
+
+#[test]
+fn reserve_works() {
+    let mut interner = StringInterner::new();
+    interner.intern("aa");
+    interner.reserve(100);
+    interner.reserve_buffer(1000);
+    assert!(interner.capacity() >= 101);
+    assert!(interner.buffer_capacity() >= 1002);
+    let aa = interner.get("aa").unwrap();
+    assert_eq!(interner.resolve(aa), Some("aa"));
+    for n in 0..100 {
+        interner.intern(n.to_string());
+    }
+    assert_eq!(interner.len(), 101);
+    assert_eq!(interner.intern("aa"), aa);
+}
 
-use fxhash::{FxBuildHasher, FxHasher};
-use string_hash_interner::{
-    DefaultHashBuilder, DefaultStringInterner as StringInterner, DefaultSymbol, Symbol,
-};
+#[test]
+fn buffer_len_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol> + 'static>() {
+        // The prefixed backend stores the length of short strings in one byte.
+        let prefix_len =
+            usize::from(TypeId::of::<B>() == TypeId::of::<PrefixedBackend<str, DefaultSymbol>>());
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        assert_eq!(interner.buffer_len(), 0);
+        interner.intern("aa");
+        interner.intern("bbb");
+        interner.intern("aa");
+        interner.intern("");
+        assert_eq!(interner.buffer_len(), 5 + 3 * prefix_len);
+        assert!(interner.buffer_len() <= interner.buffer_capacity());
+        interner.clear();
+        assert_eq!(interner.buffer_len(), 0);
+    }
+    for_each_backend!(test_for_backend);
 
-fn expect_valid_symbol<S>(index: usize) -> S
-where
-    S: Symbol,
-{
-    S::try_from_usize(index).expect("encountered invalid symbol")
+    let mut interner = Interner::<[char]>::new();
+    interner.intern(&['ü', 'b', 'e', 'r'][..]);
+    assert_eq!(interner.buffer_len(), 4);
 }
 
 #[test]
-fn new_works() {
-    let interner = StringInterner::new();
-    assert_eq!(interner.len(), 0);
-    assert!(interner.is_empty());
-    let other = StringInterner::new();
-    assert!(Iterator::eq(interner.iter(), other.iter()));
+fn try_reserve_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let aa = interner.intern("aa");
+        assert_eq!(interner.try_reserve(100), Ok(()));
+        assert_eq!(interner.try_reserve_buffer(1000), Ok(()));
+        assert!(interner.capacity() >= 101);
+        assert!(interner.buffer_capacity() >= 1000);
+        assert_eq!(
+            interner.try_reserve(usize::MAX),
+            Err(ReserveError::Dedup(
+                hashbrown::TryReserveError::CapacityOverflow
+            ))
+        );
+        assert!(interner.try_reserve_buffer(usize::MAX).is_err());
+        assert_eq!(interner.resolve(aa), Some("aa"));
+        assert_eq!(interner.intern("aa"), aa);
+    }
+    for_each_backend!(test_for_backend);
 }
 
 #[test]
-fn is_empty_works() {
+fn entry_works() {
     let mut interner = StringInterner::new();
-    assert!(interner.is_empty());
-    interner.intern("aa");
-    assert!(!interner.is_empty());
+    let aa = interner.intern("aa");
+    match interner.entry("aa") {
+        Entry::Occupied(symbol) => assert_eq!(symbol, aa),
+        Entry::Vacant(_) => panic!("expected an occupied entry"),
+    }
+    let bb = match interner.entry("bb") {
+        Entry::Occupied(_) => panic!("expected a vacant entry"),
+        Entry::Vacant(vacant) => {
+            assert_eq!(vacant.string(), "bb");
+            let hash = vacant.hash();
+            let bb = vacant.intern();
+            assert_eq!(interner.get_hash(bb), Some(hash));
+            bb
+        }
+    };
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.get("bb"), Some(bb));
+    assert_eq!(interner.entry(&String::from("bb")).symbol(), Some(bb));
+    assert_eq!(interner.entry("cc").symbol(), None);
+    assert_eq!(interner.len(), 2);
+    let cc = interner.entry("cc").or_intern();
+    assert_eq!(interner.resolve(cc), Some("cc"));
+
+    let mut interner = string_hash_interner::StringInterner::<SymbolU8>::new();
+    for n in 0..u8::MAX {
+        interner.intern(n.to_string());
+    }
+    let Entry::Vacant(vacant) = interner.entry("overflow") else {
+        panic!("expected a vacant entry");
+    };
+    assert_eq!(vacant.try_intern(), Err(InternError::SymbolOverflow));
+    assert_eq!(interner.len(), u8::MAX as usize);
 }
 
 #[test]
-fn clone_works() {
+fn intern_full_works() {
     let mut interner = StringInterner::new();
-    assert_eq!(interner.intern("aa").to_usize(), 0);
+    let (aa, hash, new) = interner.intern_full("aa");
+    assert!(new);
+    assert_eq!(interner.get_hash(aa), Some(hash));
+    assert_eq!(interner.intern_full("aa"), (aa, hash, false));
+    let (bb, _, new) = interner.intern_full("bb");
+    assert!(new);
+    assert_ne!(aa, bb);
+    assert_eq!(interner.len(), 2);
+}
 
-    let mut cloned = interner.clone();
-    assert!(Iterator::eq(interner.iter(), cloned.iter()));
-    // And the clone should have the same interned values
-    assert_eq!(cloned.intern("aa").to_usize(), 0);
+#[test]
+fn eq_works() {
+    let a = StringInterner::from_iter(["aa", "bb", "", "cc"]);
+    let b = StringInterner::from_iter(["cc", "", "aa", "bb", "aa"]);
+    let c = StringInterner::from_iter(["aa", "bb", ""]);
+    let d = StringInterner::from_iter(["aa", "bb", "", "dd"]);
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_ne!(a, d);
+    assert!(!a.eq_ordered(&b));
+    assert!(a.eq_ordered(&a.clone()));
+    assert!(!a.eq_ordered(&d));
+    assert_eq!(StringInterner::new(), StringInterner::new());
+
+    let prefixed = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        PrefixedBackend<str, DefaultSymbol>,
+    >::from_iter(["aa", "bb", "", "cc"]);
+    assert!(a == prefixed);
+    assert!(a.eq_ordered(&prefixed));
 }
 
 #[test]
-fn intern_works() {
+fn eq_strings_works() {
+    let a = StringInterner::from_iter(["aa", "bb", "", "cc"]);
+    assert_eq!(a, ["aa", "bb", "", "cc"]);
+    assert_eq!(a, ["aa", "bb", "", "cc"][..]);
+    assert_eq!(a, vec!["aa", "bb", "", "cc"]);
+    assert_eq!(a, [String::from("aa"), "bb".into(), "".into(), "cc".into()]);
+    assert_ne!(a, ["cc", "", "aa", "bb"]);
+    assert_ne!(a, ["aa", "bb", ""]);
+    assert_ne!(a, ["aa", "bb", "", "cc", "cc"]);
+    assert_eq!(StringInterner::new(), [""; 0]);
+
+    let prefixed = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        PrefixedBackend<str, DefaultSymbol>,
+    >::from_iter(["aa", "bb"]);
+    assert_eq!(prefixed, ["aa", "bb"]);
+
+    let bytes = Interner::<[u8]>::from_iter([b"ab", b"cd"]);
+    assert_eq!(bytes, [b"ab", b"cd"]);
+}
+
+#[test]
+fn hash_works() {
+    fn hash_of(interner: &StringInterner) -> u64 {
+        FixedHashBuilder::default().hash_one(interner)
+    }
+    fn hash_ordered_of(interner: &StringInterner) -> u64 {
+        let mut hasher = FixedHashBuilder::default().build_hasher();
+        interner.hash_ordered(&mut hasher);
+        hasher.finish()
+    }
+    let a = StringInterner::from_iter(["aa", "bb", "", "cc"]);
+    let b = StringInterner::from_iter(["cc", "", "aa", "bb"]);
+    let c = StringInterner::from_iter(["aa", "bb", ""]);
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert_ne!(hash_of(&a), hash_of(&c));
+    assert_ne!(hash_ordered_of(&a), hash_ordered_of(&b));
+    assert_eq!(hash_ordered_of(&a), hash_ordered_of(&a.clone()));
+
+    let mut seeded = StringInterner::with_hasher(DefaultHashBuilder::default());
+    seeded.intern_all(["bb", "cc", "aa", ""]);
+    assert_eq!(hash_of(&a), hash_of(&seeded));
+}
+
+#[test]
+fn intern_all_works() {
     let mut interner = StringInterner::new();
-    // Insert 3 unique strings:
-    let aa = interner.intern("aa").to_usize();
-    let bb = interner.intern("bb").to_usize();
-    let cc = interner.intern("cc").to_usize();
-    // All symbols must be different from each other.
-    assert_ne!(aa, bb);
-    assert_ne!(bb, cc);
-    assert_ne!(cc, aa);
-    // The length of the string interner must be 3 at this point.
+    let aa = interner.intern("aa");
+    let symbols = interner.intern_all(["bb", "aa", "", "bb"]);
+    assert_eq!(symbols.len(), 4);
+    assert_eq!(symbols[1], aa);
+    assert_eq!(symbols[0], symbols[3]);
+    assert_eq!(interner.resolve(symbols[2]), Some(""));
     assert_eq!(interner.len(), 3);
-    // Insert the same 3 unique strings, yield the same symbols:
-    assert_eq!(
-        interner.resolve(<DefaultSymbol>::try_from_usize(aa).unwrap()),
-        Some("aa")
-    );
-    assert_eq!(
-        interner.intern("aa").to_usize(),
-        aa,
-        "'aa' did not produce the same symbol",
-    );
+    assert!(interner.intern_all(Vec::<String>::new()).is_empty());
+}
+
+#[test]
+fn merge_works() {
+    let mut interner = StringInterner::new();
+    interner.intern("aa");
+    interner.intern("bb");
+    let mut other = StringInterner::with_hasher(*interner.hasher());
+    let other_symbols = ["cc", "bb", "dd"].map(|s| other.intern(s));
+
+    let remap = interner.merge(&other);
+    assert_eq!(remap.len(), other.len());
+    assert_eq!(interner.len(), 4);
+    for symbol in other_symbols {
+        let merged = remap[symbol.to_usize()];
+        assert_eq!(interner.resolve(merged), other.resolve(symbol));
+        assert_eq!(interner.get(other.resolve(symbol).unwrap()), Some(merged));
+    }
     assert_eq!(
-        interner.intern("bb").to_usize(),
-        bb,
-        "'bb' did not produce the same symbol",
+        remap[other_symbols[1].to_usize()],
+        interner.get("bb").unwrap()
     );
+}
+
+#[test]
+fn first_and_last_symbol_work() {
+    let mut interner = StringInterner::new();
+    assert_eq!(interner.first_symbol(), None);
+    assert_eq!(interner.last_symbol(), None);
+    let aa = interner.intern("aa");
+    assert_eq!(interner.first_symbol(), Some(aa));
+    assert_eq!(interner.last_symbol(), Some(aa));
+    let bb = interner.intern("bb");
+    interner.intern("aa");
+    assert_eq!(interner.first_symbol(), Some(aa));
+    assert_eq!(interner.last_symbol(), Some(bb));
+
+    let mut interner = Interner::<str, SymbolU8>::new();
+    for n in 0..=u8::MAX - 1 {
+        interner.intern(n.to_string());
+    }
+    assert_eq!(interner.last_symbol(), interner.get("254"));
+}
+
+#[test]
+fn contains_symbol_works() {
+    let mut interner = StringInterner::new();
+    let aa = interner.intern("aa");
+    let bb = interner.intern("bb");
+    assert!(interner.contains_symbol(aa));
+    assert!(interner.contains_symbol(bb));
+    assert!(!interner.contains_symbol(expect_valid_symbol(2)));
+    interner.pop();
+    assert!(!interner.contains_symbol(bb));
+    let frozen = interner.freeze();
+    assert!(frozen.contains_symbol(aa));
+    assert!(!frozen.contains_symbol(bb));
+}
+
+#[test]
+fn extend_from_interner_works() {
+    let mut interner = StringInterner::new();
+    let aa = interner.intern("aa");
+    let bb = interner.intern("bb");
+    let mut other =
+        Interner::<str, DefaultSymbol, _, BucketBackend<str, DefaultSymbol>>::with_hasher(
+            *interner.hasher(),
+        );
+    other.intern_all(["cc", "bb", "dd"]);
+
+    interner.extend_from_interner(&other);
+    assert_eq!(interner.len(), 4);
+    assert_eq!(interner.get("aa"), Some(aa));
+    assert_eq!(interner.get("bb"), Some(bb));
+    for (_, string) in &other {
+        assert!(interner.contains(string));
+    }
+    interner.extend_from_interner(&StringInterner::with_hasher(*interner.hasher()));
+    assert_eq!(interner.len(), 4);
+}
+
+#[test]
+fn get_or_intern_works() {
+    let mut interner = StringInterner::new();
+    let aa = interner.get_or_intern("aa");
+    assert_eq!(interner.get_or_intern(String::from("aa")), aa);
+    assert_eq!(interner.get_or_intern_static("aa"), aa);
+    let bb = interner.get_or_intern_static("bb");
+    assert_eq!(interner.intern("bb"), bb);
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn intern_static_works() {
+    fn test_for_backend<B: HashedBackend<str, DefaultSymbol> + Clone>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let aa = interner.intern("aa");
+        let bb = interner.intern_static("bb");
+        let cc = interner.intern(String::from("cc"));
+        assert_eq!(interner.intern_static("aa"), aa);
+        assert_eq!(interner.intern("bb"), bb);
+        assert_eq!(interner.try_intern_static("cc"), Ok(cc));
+        assert!(interner.iter().eq([(aa, "aa"), (bb, "bb"), (cc, "cc")]));
+        assert_eq!(interner.byte_len(bb), Some(2));
+        assert!(interner.get_hash(bb).is_some());
+
+        let interner = interner.clone().rehash_with(FixedHashBuilder::default());
+        assert_eq!(interner.get("bb"), Some(bb));
+        assert_eq!(interner.resolve(bb), Some("bb"));
+    }
+    for_each_backend!(hashed test_for_backend);
+
+    static LONG: &str = "a string that is long enough to be worth not copying";
+    let mut interner = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        BucketBackend<str, DefaultSymbol>,
+    >::new();
+    let long = interner.intern_static(LONG);
+    assert_eq!(interner.buffer_capacity(), 0);
+    assert!(std::ptr::eq(interner.resolve(long).unwrap(), LONG));
+
+    let mut interner = string_hash_interner::StringInterner::<SymbolU8>::new();
+    for n in 0..u8::MAX {
+        interner.intern(n.to_string());
+    }
     assert_eq!(
-        interner.intern("cc").to_usize(),
-        cc,
-        "'cc' did not produce the same symbol",
+        interner.try_intern_static("static"),
+        Err(InternError::SymbolOverflow)
     );
-    assert_eq!(interner.len(), 3);
 }
 
 #[test]
@@ -97,6 +1483,34 @@ fn resolve_works() {
     assert_eq!(interner.resolve(dd), None);
 }
 
+#[test]
+fn try_resolve_works() {
+    let mut interner = StringInterner::new();
+    let aa = interner.intern("aa");
+    let empty = interner.intern("");
+    assert_eq!(interner.try_resolve(aa), Ok("aa"));
+    assert_eq!(interner.try_resolve(empty), Ok(""));
+
+    let error = interner.try_resolve(expect_valid_symbol(1000)).unwrap_err();
+    assert_eq!(
+        error,
+        ResolveError::OutOfRange {
+            index: 1000,
+            len: 2
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "symbol index 1000 is out of range for an interner of 2 strings"
+    );
+
+    interner.clear();
+    assert_eq!(
+        interner.try_resolve(aa),
+        Err(ResolveError::OutOfRange { index: 0, len: 0 })
+    );
+}
+
 #[test]
 fn resolve_unchecked_works() {
     let mut interner = StringInterner::new();
@@ -117,6 +1531,67 @@ fn resolve_unchecked_works() {
     assert_ne!(cc, dd);
 }
 
+#[test]
+fn resolve_into_works() {
+    let mut interner = StringInterner::new();
+    let [aa, bb, cc] = ["aa", "bb", "cc"].map(|s| interner.intern(s));
+
+    let mut out = vec!["garbage"];
+    for symbols in [&[aa, bb, cc][..], &[cc, cc, aa], &[], &[bb]] {
+        let expected = symbols
+            .iter()
+            .map(|&sym| interner.resolve(sym))
+            .collect::<Option<Vec<_>>>()
+            .unwrap();
+
+        assert!(interner.resolve_into(symbols, &mut out));
+        assert_eq!(out, expected);
+
+        out.push("garbage");
+        unsafe { interner.resolve_into_unchecked(symbols, &mut out) };
+        assert_eq!(out, expected);
+    }
+
+    assert!(!interner.resolve_into(&[aa, expect_valid_symbol(1000), bb], &mut out));
+    assert_eq!(out, ["aa"]);
+}
+
+#[test]
+fn byte_len_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let words = ["", "a", "hello", "", "über"];
+        let symbols = words.map(|word| interner.intern(word));
+        for (word, symbol) in words.iter().zip(symbols) {
+            assert_eq!(interner.byte_len(symbol), Some(word.len()));
+            assert_eq!(unsafe { interner.byte_len_unchecked(symbol) }, word.len());
+        }
+        assert_eq!(interner.byte_len(expect_valid_symbol(1000)), None);
+    }
+    for_each_backend!(test_for_backend);
+
+    let mut interner = Interner::<[char]>::new();
+    let symbol = interner.intern(&['ü', 'b', 'e', 'r'][..]);
+    assert_eq!(interner.byte_len(symbol), Some(4));
+}
+
+#[test]
+fn resolve_many_works() {
+    let mut interner = StringInterner::new();
+    let [aa, bb, cc] = ["aa", "bb", "cc"].map(|s| interner.intern(s));
+
+    let symbols = [cc, aa, cc, bb];
+    assert!(interner
+        .resolve_many(&symbols)
+        .eq(["cc", "aa", "cc", "bb"].map(Some)));
+    assert!(unsafe { interner.resolve_many_unchecked(&symbols) }.eq(["cc", "aa", "cc", "bb"]));
+    assert_eq!(interner.resolve_many(&[]).len(), 0);
+    assert!(interner
+        .resolve_many(&[aa, expect_valid_symbol(1000)])
+        .eq([Some("aa"), None]));
+}
+
 #[test]
 fn get_works() {
     let mut interner = StringInterner::new();
@@ -137,6 +1612,74 @@ fn get_works() {
     assert_eq!(interner.len(), 3);
 }
 
+#[test]
+fn contains_works() {
+    let mut interner = StringInterner::new();
+    interner.intern("aa");
+    interner.intern("");
+    assert!(interner.contains("aa"));
+    assert!(interner.contains(""));
+    assert!(!interner.contains("bb"));
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn empty_string_works() {
+    let build_hasher = DefaultHashBuilder::default();
+    let mut interner = StringInterner::with_hasher(build_hasher);
+
+    let symbols = ["", "a", "", "b", ""].map(|s| interner.intern(s));
+    assert_eq!(interner.len(), 3);
+
+    let [e0, a, e1, b, e2] = symbols;
+    assert_eq!(e0, e1);
+    assert_eq!(e0, e2);
+    assert_ne!(e0, a);
+    assert_ne!(e0, b);
+
+    assert_eq!(interner.resolve(e0), Some(""));
+    assert_eq!(interner.resolve(a), Some("a"));
+    assert_eq!(interner.resolve(b), Some("b"));
+    assert_eq!(interner.get(""), Some(e0));
+    assert_eq!(interner.get_hash(e0), Some(build_hasher.hash_one("")));
+    assert!(Iterator::eq(
+        interner.iter(),
+        [(e0, ""), (a, "a"), (b, "b")]
+    ));
+}
+
+#[test]
+fn symbol_works() {
+    let mut interner = StringInterner::new();
+    let aa = interner.intern("aa");
+    let bb = interner.intern("bb");
+    assert_eq!(interner.symbol("aa"), aa);
+    assert_eq!(interner.symbol(String::from("bb")), bb);
+}
+
+#[test]
+fn index_works() {
+    let mut interner = StringInterner::new();
+    let aa = interner.intern("aa");
+    let empty = interner.intern("");
+    assert_eq!(&interner[aa], "aa");
+    assert_eq!(&interner[empty], "");
+}
+
+#[test]
+#[should_panic(expected = "symbol is not valid for the interner")]
+fn index_panics() {
+    let interner = StringInterner::new();
+    let _ = &interner[expect_valid_symbol::<DefaultSymbol>(0)];
+}
+
+#[test]
+#[should_panic(expected = "string is not interned")]
+fn symbol_panics() {
+    let interner = StringInterner::from_iter(["aa", "bb"]);
+    interner.symbol("cc");
+}
+
 #[test]
 fn from_iter_works() {
     let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
@@ -156,35 +1699,258 @@ fn from_iter_works() {
 }
 
 #[test]
-fn extend_works() {
-    let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
-    let expected = {
-        let mut interner = StringInterner::new();
-        for &string in &strings {
-            interner.intern(string);
+fn extend_works() {
+    let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
+    let expected = {
+        let mut interner = StringInterner::new();
+        for &string in &strings {
+            interner.intern(string);
+        }
+        interner
+    };
+    let actual = {
+        let mut interner = StringInterner::new();
+        interner.extend(strings.iter().copied());
+        interner
+    };
+    assert_eq!(actual.len(), strings.len());
+    assert!(Iterator::eq(actual.iter(), expected.iter()));
+}
+
+#[test]
+fn iter_works() {
+    let mut interner = StringInterner::new();
+    let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
+
+    let symbols = strings
+        .iter()
+        .map(|&s| (interner.intern(s), s))
+        .collect::<Vec<_>>();
+
+    assert!(Iterator::eq(symbols.into_iter(), &interner));
+}
+
+#[test]
+fn iter_remaining_works() {
+    let interner = StringInterner::from_iter(["aa", "bb", "cc", "dd"]);
+
+    let mut iter = interner.iter();
+    assert_eq!(iter.remaining(), 4);
+    assert_eq!(iter.current_index(), 0);
+    assert_eq!(iter.next().map(|(sym, _)| sym.to_usize()), Some(0));
+    assert_eq!(iter.remaining(), 3);
+    assert_eq!(iter.current_index(), 1);
+    iter.next();
+    iter.next();
+    assert_eq!(iter.remaining(), 1);
+    assert_eq!(iter.current_index(), 3);
+    assert_eq!(iter.next(), Some((expect_valid_symbol(3), "dd")));
+    assert_eq!(iter.remaining(), 0);
+    assert_eq!(iter.current_index(), 4);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.remaining(), 0);
+
+    let mut iter = interner.iter_with_hashes();
+    iter.next();
+    assert_eq!(iter.remaining(), 3);
+    assert_eq!(iter.current_index(), 1);
+}
+
+#[test]
+fn into_iter_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let strings = ["aa", "bb", "", "cc"];
+        let interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::from_iter(
+                strings,
+            );
+        let symbols = strings.map(|s| interner.get(s).unwrap());
+        let mut iter = interner.into_iter();
+        assert_eq!(iter.len(), 4);
+        let owned: Vec<(DefaultSymbol, String)> = iter.by_ref().collect();
+        assert_eq!(iter.next(), None);
+        assert_eq!(
+            owned,
+            symbols
+                .into_iter()
+                .zip(strings.map(String::from))
+                .collect::<Vec<_>>()
+        );
+    }
+    for_each_backend!(test_for_backend);
+
+    let interner = Interner::<CStr>::from_iter([c"aa", c"bb"]);
+    let owned = interner
+        .into_iter()
+        .map(|(_, s)| s)
+        .collect::<Vec<CString>>();
+    assert_eq!(owned, [c"aa".to_owned(), c"bb".to_owned()]);
+}
+
+#[test]
+fn single_field_iters_work() {
+    fn test_for_backend<B: HashedBackend<str, DefaultSymbol>>() {
+        let interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::from_iter(
+                ["aa", "bb", "", "cc"],
+            );
+        assert_eq!(interner.symbols().len(), 4);
+        assert_eq!(interner.strings().len(), 4);
+        assert_eq!(interner.hashes().len(), 4);
+        let symbols = interner.symbols().collect::<Vec<_>>();
+        let strings = interner.strings().collect::<Vec<_>>();
+        let hashes = interner.hashes().collect::<Vec<_>>();
+        for (i, (symbol, string, hash)) in interner.iter_with_hashes().enumerate() {
+            assert_eq!(symbols[i], symbol);
+            assert_eq!(strings[i], string);
+            assert_eq!(hashes[i], hash);
+        }
+    }
+    for_each_backend!(hashed test_for_backend);
+}
+
+#[test]
+fn iter_len_works() {
+    fn test_for_backend<B: HashedBackend<str, DefaultSymbol>>() {
+        let interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::from_iter(
+                ["aa", "bb", "", "cc"],
+            );
+        assert_eq!(interner.iter().len(), interner.len());
+        assert_eq!(interner.iter_with_hashes().len(), interner.len());
+
+        let mut iter = interner.iter();
+        iter.next();
+        assert_eq!(iter.len(), 3);
+        iter.by_ref().for_each(drop);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = interner.iter_with_hashes().fuse();
+        iter.nth(1);
+        assert_eq!(iter.len(), 2);
+    }
+    for_each_backend!(hashed test_for_backend);
+}
+
+#[test]
+fn sorted_iter_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let strings = ["bb", "", "ab", "b", "aa", "bb"];
+        let interner = strings
+            .iter()
+            .collect::<string_hash_interner::StringInterner<DefaultSymbol, DefaultHashBuilder, B>>(
+            );
+        let mut expected: Vec<_> = interner.iter().collect();
+        expected.sort_by_key(|&(_, string)| string);
+        assert!(interner.sorted_iter().eq(expected));
+        assert_eq!(interner.sorted_iter().len(), 5);
+    }
+    for_each_backend!(test_for_backend);
+
+    let interner = Interner::<[u8]>::from_iter([&b"\xff"[..], b"\x00", b""]);
+    assert!(interner
+        .sorted_iter()
+        .map(|(_, string)| string)
+        .eq([&b""[..], b"\x00", b"\xff"]));
+}
+
+#[test]
+fn freeze_works() {
+    let mut interner = StringInterner::from_iter(["aa", "bb", "cc"]);
+    let bb = interner.get("bb").unwrap();
+    let hash = interner.get_hash(bb);
+    let frozen = std::sync::Arc::new(interner.clone().freeze());
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                assert_eq!(frozen.len(), 3);
+                assert_eq!(frozen.get("bb"), Some(bb));
+                assert!(!frozen.contains("dd"));
+                assert_eq!(frozen.resolve(bb), Some("bb"));
+                assert_eq!(&frozen[bb], "bb");
+                assert_eq!(frozen.get_hash(bb), hash);
+                assert!(frozen.iter().eq(interner.iter()));
+            });
+        }
+    });
+
+    let mut thawed = std::sync::Arc::into_inner(frozen).unwrap().thaw();
+    assert_eq!(thawed.intern("dd"), interner.intern("dd"));
+    assert!(thawed.eq_ordered(&interner));
+}
+
+#[test]
+fn iter_nth_works() {
+    let interner = StringInterner::from_iter(["aa", "", "bb", "cc", "dd"]);
+
+    for start in 0..=interner.len() {
+        for n in 0..=interner.len() + 1 {
+            let mut iter = interner.iter();
+            let mut linear = interner.iter();
+            for _ in 0..start {
+                iter.next();
+                linear.next();
+            }
+            for _ in 0..n {
+                linear.next();
+            }
+            assert_eq!(iter.nth(n), linear.next());
+            assert_eq!(iter.current_index(), linear.current_index());
+            assert!(Iterator::eq(iter, linear));
+
+            let mut iter = interner.iter_with_hashes();
+            let mut linear = interner.iter_with_hashes();
+            for _ in 0..start {
+                iter.next();
+                linear.next();
+            }
+            for _ in 0..n {
+                linear.next();
+            }
+            assert_eq!(iter.nth(n), linear.next());
+            assert!(Iterator::eq(iter, linear));
         }
-        interner
-    };
-    let actual = {
-        let mut interner = StringInterner::new();
-        interner.extend(strings.iter().copied());
-        interner
-    };
-    assert_eq!(actual.len(), strings.len());
-    assert!(Iterator::eq(actual.iter(), expected.iter()));
+    }
+    assert_eq!(interner.iter().last(), Some((expect_valid_symbol(4), "dd")));
+    assert_eq!(
+        interner.iter_with_hashes().last(),
+        interner.iter_with_hashes().collect::<Vec<_>>().pop()
+    );
+    assert_eq!(StringInterner::new().iter().last(), None);
+    assert_eq!(StringInterner::new().iter_with_hashes().last(), None);
 }
 
 #[test]
-fn iter_works() {
-    let mut interner = StringInterner::new();
-    let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
+fn iter_from_works() {
+    let interner = StringInterner::from_iter(["aa", "", "bb", "cc", "dd"]);
 
-    let symbols = strings
-        .iter()
-        .map(|&s| (interner.intern(s), s))
-        .collect::<Vec<_>>();
+    for k in 0..=interner.len() + 1 {
+        assert!(Iterator::eq(
+            interner.iter_from(expect_valid_symbol(k)),
+            interner.iter().skip(k)
+        ));
+    }
+    assert_eq!(
+        interner.iter_from(expect_valid_symbol(3)).current_index(),
+        3
+    );
+    assert_eq!(interner.iter_from(expect_valid_symbol(1000)).next(), None);
+}
 
-    assert!(Iterator::eq(symbols.into_iter(), &interner));
+#[test]
+fn span_works() {
+    let interner = StringInterner::from_iter(["aa", "", "bb", "cc", "dd"]);
+    assert_eq!(interner.buffer(), b"aabbccdd");
+
+    for (symbol, string) in &interner {
+        let (from, to) = interner.span(symbol).unwrap();
+        assert_eq!(unsafe { interner.span_unchecked(symbol) }, (from, to));
+        assert_eq!(&interner.buffer()[from..to], string.as_bytes());
+    }
+    assert_eq!(interner.span(expect_valid_symbol(1)), Some((2, 2)));
+    assert_eq!(interner.span(expect_valid_symbol(1000)), None);
 }
 
 #[test]
@@ -217,6 +1983,7 @@ fn shrink_to_fit_works() {
 
 #[test]
 fn correct_hashes() {
+    #[allow(clippy::manual_hash_one)]
     fn make_hash(build_hasher: impl BuildHasher, s: &str) -> u64 {
         let mut hasher = build_hasher.build_hasher();
         s.hash(&mut hasher);
@@ -256,6 +2023,30 @@ fn correct_fxhashes() {
     }
 }
 
+#[test]
+fn with_seed_works() {
+    let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
+
+    let mut a =
+        string_hash_interner::StringInterner::<DefaultSymbol, FixedHashBuilder>::with_seed(42);
+    let mut b =
+        string_hash_interner::StringInterner::<DefaultSymbol, FixedHashBuilder>::with_seed(42);
+    let mut c =
+        string_hash_interner::StringInterner::<DefaultSymbol, FixedHashBuilder>::with_seed(43);
+
+    for s in strings {
+        let (_, a_hash) = a.intern_and_hash(s);
+        let (_, b_hash) = b.intern_and_hash(s);
+        let (_, c_hash) = c.intern_and_hash(s);
+        assert_eq!(a_hash, b_hash);
+        assert_ne!(a_hash, c_hash);
+        assert_eq!(a_hash, FixedHashBuilder::with_seed(42).hash_one(s));
+    }
+    for (sym, s) in &a {
+        assert_eq!(a.get_hash(sym), b.get_hash(b.get(s).unwrap()));
+    }
+}
+
 #[test]
 fn manual_hashmap() {
     // Force at least one rehashing
@@ -297,6 +2088,64 @@ fn manual_hashmap() {
     }
 }
 
+#[test]
+fn get_with_hash_works() {
+    let build_hasher = DefaultHashBuilder::default();
+
+    let mut a = StringInterner::with_hasher(build_hasher);
+    let mut b = StringInterner::with_hasher(build_hasher);
+
+    let a_symbols = ["aa", "bb", "cc", "dd"].map(|s| a.intern(s));
+    let b_symbols = ["dd", "ee", "bb", "aa"].map(|s| b.intern(s));
+
+    let translate = |sym| {
+        let string = a.resolve(sym).unwrap();
+        let hash = a.get_hash(sym).unwrap();
+        b.get_with_hash(string, hash)
+    };
+
+    assert_eq!(translate(a_symbols[0]), Some(b_symbols[3]));
+    assert_eq!(translate(a_symbols[1]), Some(b_symbols[2]));
+    assert_eq!(translate(a_symbols[2]), None);
+    assert_eq!(translate(a_symbols[3]), Some(b_symbols[0]));
+}
+
+#[test]
+fn intern_and_resolve_works() {
+    fn test_for_backend<B: Backend<str, DefaultSymbol>>() {
+        let mut interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::new();
+        let (aa, string) = interner.intern_and_resolve("aa");
+        assert_eq!(string, "aa");
+        let ptr = string.as_ptr();
+        assert_eq!(interner.resolve(aa).unwrap().as_ptr(), ptr);
+
+        let (bb, string) = interner.intern_and_resolve(String::from("bb"));
+        assert_eq!(string, "bb");
+        let (again, string) = interner.intern_and_resolve("aa");
+        assert_eq!(again, aa);
+        assert_eq!(string, "aa");
+        assert_eq!(interner.resolve(bb), Some("bb"));
+        assert_eq!(interner.len(), 2);
+    }
+    for_each_backend!(test_for_backend);
+}
+
+#[test]
+fn intern_with_hash_works() {
+    let build_hasher = DefaultHashBuilder::default();
+    let mut interner = StringInterner::with_hasher(build_hasher);
+    let aa = interner.intern_with_hash("aa", build_hasher.hash_one("aa"));
+    assert_eq!(interner.get("aa"), Some(aa));
+    assert_eq!(interner.get_hash(aa), Some(build_hasher.hash_one("aa")));
+    assert_eq!(interner.intern("aa"), aa);
+    assert_eq!(
+        interner.intern_with_hash("aa", build_hasher.hash_one("aa")),
+        aa
+    );
+    assert_eq!(interner.len(), 1);
+}
+
 #[test]
 fn iter_with_hashes() {
     let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
@@ -304,6 +2153,7 @@ fn iter_with_hashes() {
 
     let mut interner = StringInterner::with_hasher(build_hasher);
 
+    #[allow(clippy::manual_hash_one)]
     let make_hash = |s: &str| {
         let mut hasher = build_hasher.build_hasher();
         s.hash(&mut hasher);
@@ -318,17 +2168,262 @@ fn iter_with_hashes() {
     assert!(Iterator::eq(interner.iter_with_hashes(), expected));
 }
 
+#[test]
+fn resolve_with_hash_works() {
+    fn test_for_backend<B: HashedBackend<str, DefaultSymbol>>() {
+        let interner =
+            string_hash_interner::StringInterner::<DefaultSymbol, DefaultHashBuilder, B>::from_iter(
+                ["aa", "bb", "", "cc"],
+            );
+        for (symbol, string, hash) in interner.iter_with_hashes() {
+            assert_eq!(interner.resolve_with_hash(symbol), Some((string, hash)));
+            // SAFETY: The symbol was yielded by the interner.
+            let resolved = unsafe { interner.resolve_with_hash_unchecked(symbol) };
+            assert_eq!(resolved, (string, hash));
+        }
+        assert_eq!(interner.resolve_with_hash(expect_valid_symbol(4)), None);
+    }
+    for_each_backend!(hashed test_for_backend);
+}
+
+#[test]
+fn resolve_table_works() {
+    let strings = ["aa", "bb", "cc", "dd", "ee", "ff"];
+    let interner = StringInterner::from_iter(strings);
+    let table = interner.resolve_table();
+
+    assert_eq!(table.len(), interner.len());
+    assert!(Iterator::eq(table.iter(), interner.iter()));
+    assert!(Iterator::eq(
+        table.iter_with_hashes(),
+        interner.iter_with_hashes()
+    ));
+    for (sym, s) in &interner {
+        assert_eq!(table.resolve(sym), Some(s));
+        assert_eq!(unsafe { table.resolve_unchecked(sym) }, s);
+        assert_eq!(table.get_hash(sym), interner.get_hash(sym));
+    }
+    assert_eq!(table.resolve(expect_valid_symbol(1000)), None);
+    assert_eq!(table.get_hash(expect_valid_symbol(1000)), None);
+}
+
+#[test]
+fn resolve_index_works() {
+    let mut interner = StringInterner::from_iter(["aa", "", "bbb", "c"]);
+    let index = interner.build_resolve_index().unwrap();
+
+    assert_eq!(index.len(), interner.len());
+    for (sym, s) in &interner {
+        assert_eq!(index.resolve(sym), Some(s));
+        assert_eq!(unsafe { index.resolve_unchecked(sym) }, s);
+    }
+    assert_eq!(index.resolve(expect_valid_symbol(4)), None);
+
+    let dd = interner.intern("dd");
+    let index = interner.build_resolve_index().unwrap();
+    assert_eq!(index.resolve(dd), Some("dd"));
+
+    let empty = StringInterner::new();
+    assert!(empty.build_resolve_index().unwrap().is_empty());
+
+    let mut chars = Interner::<[char]>::new();
+    let uber = chars.intern(&['ü', 'b', 'e', 'r'][..]);
+    let index = chars.build_resolve_index().unwrap();
+    assert_eq!(index.resolve(uber), Some(&['ü', 'b', 'e', 'r'][..]));
+}
+
+#[test]
+fn from_parts_works() {
+    let interner = StringInterner::from_iter(["aa", "", "bb", "cc"]);
+    let (buffer, ends, hashes) = interner.as_parts();
+    assert_eq!(buffer, b"aabbcc");
+    assert_eq!(ends, [2, 2, 4, 6]);
+    assert_eq!(hashes.len(), interner.len());
+
+    let mut restored = unsafe {
+        StringInterner::from_parts(
+            buffer.to_vec(),
+            ends.to_vec(),
+            hashes.to_vec(),
+            *interner.hasher(),
+        )
+    };
+    assert!(restored.eq_ordered(&interner));
+    for (symbol, string) in &interner {
+        assert_eq!(restored.get(string), Some(symbol));
+    }
+    assert_eq!(restored.intern("dd").to_usize(), 4);
+
+    let empty = unsafe {
+        StringInterner::from_parts(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            DefaultHashBuilder::default(),
+        )
+    };
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn into_parts_works() {
+    let interner = StringInterner::from_iter(["aa", "", "bb", "cc"]);
+    let buffer_ptr = interner.buffer().as_ptr();
+    let (buffer, ends, hashes, hasher) = interner.clone().into_parts();
+    assert_eq!(buffer, b"aabbcc");
+    assert_eq!(ends, [2, 2, 4, 6]);
+    assert_eq!(hashes, interner.as_parts().2);
+
+    let restored = unsafe { StringInterner::from_parts(buffer, ends, hashes, hasher) };
+    assert!(restored.eq_ordered(&interner));
+    for (symbol, string) in &interner {
+        assert_eq!(restored.get(string), Some(symbol));
+    }
+
+    // The buffer is moved back and forth without copying.
+    let mut restored = {
+        let (buffer, ends, hashes, hasher) = interner.into_parts();
+        assert_eq!(buffer.as_ptr(), buffer_ptr);
+        unsafe { StringInterner::from_parts(buffer, ends, hashes, hasher) }
+    };
+    assert_eq!(restored.buffer().as_ptr(), buffer_ptr);
+    assert_eq!(restored.intern("bb").to_usize(), 2);
+    assert_eq!(restored.intern("dd").to_usize(), 4);
+}
+
+#[test]
+fn resolve_table_from_parts() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let interner = StringInterner::from_iter(["", "aa", "bbb", "", "c"]);
+
+    let mut buffer = Vec::new();
+    let mut ends = Vec::new();
+    let mut hashes = Vec::new();
+    for (_sym, s, hash) in interner.iter_with_hashes() {
+        buffer.extend_from_slice(s.as_bytes());
+        ends.push(buffer.len() as u32);
+        hashes.push(hash);
+    }
+
+    // SAFETY: The parts are built from the strings of a valid interner.
+    let table = unsafe { ResolveTable::<str, DefaultSymbol, u32>::new(&buffer, &ends, &hashes) };
+    assert_send_sync(&table);
+
+    assert_eq!(table.len(), interner.len());
+    assert!(!table.is_empty());
+    assert!(Iterator::eq(
+        table.iter_with_hashes(),
+        interner.iter_with_hashes()
+    ));
+    for (sym, s) in table {
+        assert_eq!(interner.resolve(sym), Some(s));
+    }
+}
+
+#[test]
+fn prefixed_backend_works() {
+    // Make sure lengths which take multiple bytes to encode are handled.
+    let strings = [
+        "",
+        "a",
+        &"b".repeat(127),
+        &"c".repeat(128),
+        "",
+        &"d".repeat(20000),
+    ];
+
+    let mut interner = string_hash_interner::StringInterner::<
+        DefaultSymbol,
+        DefaultHashBuilder,
+        PrefixedBackend<_, _>,
+    >::new();
+    let symbols = strings.map(|s| interner.intern(s));
+
+    assert_eq!(interner.len(), 5);
+    assert_eq!(symbols[0], symbols[4]);
+    for (&sym, &s) in symbols.iter().zip(&strings) {
+        assert_eq!(interner.resolve(sym), Some(s));
+        assert_eq!(unsafe { interner.resolve_unchecked(sym) }, s);
+        assert_eq!(interner.get(s), Some(sym));
+    }
+    assert!(Iterator::eq(
+        interner.iter(),
+        [0, 1, 2, 3, 5].map(|i| (symbols[i], strings[i]))
+    ));
+    assert_eq!(interner.resolve(expect_valid_symbol(1000)), None);
+}
+
+#[test]
+fn into_str_interner_works() {
+    let build_hasher = DefaultHashBuilder::default();
+    let mut bytes = Interner::<[u8]>::with_hasher(build_hasher);
+    let symbols = [&b"aa"[..], b"", "\u{1F600}".as_bytes(), b"bb"].map(|s| bytes.intern(s));
+
+    let strings = bytes.into_str_interner().unwrap();
+    assert_eq!(strings.len(), 4);
+    for (sym, s) in symbols.into_iter().zip(["aa", "", "\u{1F600}", "bb"]) {
+        assert_eq!(strings.resolve(sym), Some(s));
+        assert_eq!(strings.get(s), Some(sym));
+        assert_eq!(strings.get_hash(sym), Some(build_hasher.hash_one(s)));
+    }
+
+    let bytes = strings.into_byte_interner();
+    assert_eq!(bytes.len(), 4);
+    for (sym, s) in symbols.into_iter().zip(["aa", "", "\u{1F600}", "bb"]) {
+        assert_eq!(bytes.resolve(sym), Some(s.as_bytes()));
+        assert_eq!(bytes.get(s.as_bytes()), Some(sym));
+        assert_eq!(
+            bytes.get_hash(sym),
+            Some(build_hasher.hash_one(s.as_bytes()))
+        );
+    }
+}
+
+#[test]
+fn into_str_interner_fails() {
+    let invalid = [b'a', 0xff, b'b'];
+    let bytes = Interner::<[u8]>::from_iter([&b"aa"[..], &invalid, b"\xc3"]);
+
+    let error = bytes.into_str_interner().unwrap_err();
+    assert_eq!(error.valid_up_to(), 1);
+    assert_eq!(error.error_len(), Some(1));
+}
+
+#[test]
+fn get_str_works() {
+    let long = "a".repeat(1000);
+    let strings = ["aa", "", "bb", &long].map(|s| CString::new(s).unwrap());
+    let interner = Interner::<CStr>::from_iter(&strings);
+
+    for (s, string) in ["aa", "", "bb", &long].into_iter().zip(&strings) {
+        assert_eq!(interner.get_str(s), Ok(interner.get(string)));
+        assert!(interner.get(string).is_some());
+    }
+    assert_eq!(interner.get_str("cc"), Ok(None));
+    assert_eq!(interner.get_str(&long[1..]), Ok(None));
+    assert_eq!(
+        interner.get_str("a\0a").unwrap_err().nul_position(),
+        CString::new("a\0a").unwrap_err().nul_position()
+    );
+}
+
 mod different_strings {
     use std::{
         borrow::Borrow,
         ffi::{CStr, CString, OsStr},
+        fmt::Debug,
         hash::{BuildHasher, Hasher},
+        path::Path,
     };
 
     use hashbrown::DefaultHashBuilder;
-    use string_hash_interner::{Intern, Interner};
+    use string_hash_interner::{
+        backend::{BucketBackend, HashedBackend, PrefixedBackend, StringBackend},
+        DefaultSymbol, Intern, Interner, Symbol,
+    };
 
-    trait TestString: Intern + ToOwned + AsRef<Self> {
+    trait TestString: Intern + ToOwned + AsRef<Self> + Debug {
         fn make(s: &str) -> Self::Owned;
 
         fn data(data: impl IntoIterator<Item = &'static str>) -> Vec<Self::Owned> {
@@ -354,6 +2449,12 @@ mod different_strings {
         }
     }
 
+    impl TestString for Path {
+        fn make(s: &str) -> Self::Owned {
+            From::from(s)
+        }
+    }
+
     impl TestString for [u8] {
         fn make(s: &str) -> Self::Owned {
             s.as_bytes().to_vec()
@@ -366,17 +2467,30 @@ mod different_strings {
         }
     }
 
-    fn general_test<I: TestString + ?Sized>() {
+    impl TestString for [u16] {
+        fn make(s: &str) -> Self::Owned {
+            s.encode_utf16().collect()
+        }
+    }
+
+    impl TestString for [u32] {
+        fn make(s: &str) -> Self::Owned {
+            s.chars().map(u32::from).collect()
+        }
+    }
+
+    fn general_test<I: TestString + ?Sized, B: HashedBackend<I, DefaultSymbol>>() {
         let strings = I::data(["aa", "bb", "cc", "dd", "ee", "ff"]);
 
         let build_hasher = DefaultHashBuilder::default();
+        #[allow(clippy::manual_hash_one)]
         let make_hash = |s: &I| {
             let mut hasher = build_hasher.build_hasher();
             s.hash(&mut hasher);
             hasher.finish()
         };
 
-        let mut interner = Interner::<I>::with_hasher(build_hasher);
+        let mut interner = Interner::<I, DefaultSymbol, _, B>::with_hasher(build_hasher);
 
         let expected = strings
             .iter()
@@ -384,15 +2498,418 @@ mod different_strings {
             .map(|s| (interner.intern(s), s, make_hash(s)))
             .collect::<Vec<_>>();
 
-        assert!(Iterator::eq(interner.iter_with_hashes(), expected));
+        assert!(Iterator::eq(interner.iter_with_hashes(), expected.clone()));
+        assert!(Iterator::eq(
+            interner.iter(),
+            expected.iter().map(|&(sym, s, _)| (sym, s))
+        ));
+        for (sym, s, hash) in expected {
+            assert_eq!(interner.resolve(sym), Some(s));
+            let owned = interner.resolve_owned(sym).unwrap();
+            assert_eq!(Borrow::<I>::borrow(&owned), s);
+            assert_eq!(interner.get_hash(sym), Some(hash));
+            assert_eq!(interner.get(s), Some(sym));
+        }
+
+        empty_string_test::<I, B>();
+    }
+
+    fn empty_string_test<I: TestString + ?Sized, B: HashedBackend<I, DefaultSymbol>>() {
+        let strings = I::data(["", "aa", "", "b", ""]);
+        let [empty, aa, _, b, _] = [0, 1, 2, 3, 4].map(|i| Borrow::<I>::borrow(&strings[i]));
+
+        // Interned first, it gets the first symbol.
+        let mut interner = Interner::<I, DefaultSymbol, DefaultHashBuilder, B>::new();
+        let symbols = strings.iter().map(|s| interner.intern(s.borrow()));
+        let symbols = symbols.map(|sym| sym.to_usize()).collect::<Vec<_>>();
+        assert_eq!(symbols, [0, 1, 0, 2, 0]);
+        assert_eq!(interner.len(), 3);
+        assert!(Iterator::eq(
+            interner.iter().map(|(_, s)| s),
+            [empty, aa, b]
+        ));
+
+        // Interned between other strings, its zero-length span doesn't alias the neighbors.
+        let mut interner = Interner::<I, DefaultSymbol, DefaultHashBuilder, B>::new();
+        let symbols = [aa, empty, b, empty, aa].map(|s| interner.intern(s));
+        assert_eq!(symbols.map(|sym| sym.to_usize()), [0, 1, 2, 1, 0]);
+        assert_eq!(interner.resolve(symbols[0]), Some(aa));
+        assert_eq!(interner.resolve(symbols[1]), Some(empty));
+        assert_eq!(interner.resolve(symbols[2]), Some(b));
+        assert_eq!(interner.get(empty), Some(symbols[1]));
+        assert_eq!(interner.byte_len(symbols[1]), Some(empty.as_bytes().len()));
+        assert!(Iterator::eq(
+            interner.iter().map(|(_, s)| s),
+            [aa, empty, b]
+        ));
     }
 
     #[test]
     fn all_string_types() {
-        general_test::<str>();
-        general_test::<CStr>();
-        general_test::<OsStr>();
-        general_test::<[u8]>();
-        general_test::<[char]>();
+        general_test::<str, StringBackend<_, _>>();
+        general_test::<CStr, StringBackend<_, _>>();
+        general_test::<OsStr, StringBackend<_, _>>();
+        general_test::<Path, StringBackend<_, _>>();
+        general_test::<[u8], StringBackend<_, _>>();
+        general_test::<[char], StringBackend<_, _>>();
+        general_test::<[u16], StringBackend<_, _>>();
+        general_test::<[u32], StringBackend<_, _>>();
+    }
+
+    #[test]
+    fn all_string_types_prefixed() {
+        general_test::<str, PrefixedBackend<_, _>>();
+        general_test::<CStr, PrefixedBackend<_, _>>();
+        general_test::<OsStr, PrefixedBackend<_, _>>();
+        general_test::<Path, PrefixedBackend<_, _>>();
+        general_test::<[u8], PrefixedBackend<_, _>>();
+    }
+
+    #[test]
+    fn all_string_types_bucket() {
+        general_test::<str, BucketBackend<_, _>>();
+        general_test::<CStr, BucketBackend<_, _>>();
+        general_test::<OsStr, BucketBackend<_, _>>();
+        general_test::<Path, BucketBackend<_, _>>();
+        general_test::<[u8], BucketBackend<_, _>>();
+        general_test::<[char], BucketBackend<_, _>>();
+        general_test::<[u16], BucketBackend<_, _>>();
+        general_test::<[u32], BucketBackend<_, _>>();
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::*;
+
+    fn round_trip<I>(strings: &[&I])
+    where
+        I: string_hash_interner::Intern + AsRef<I> + std::fmt::Debug + ::serde::Serialize + ?Sized,
+        Box<I>: for<'de> ::serde::Deserialize<'de>,
+    {
+        let interner = strings.iter().collect::<Interner<I>>();
+        let json = serde_json::to_string(&interner).unwrap();
+        let deserialized: Interner<I> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.len(), interner.len());
+        for (symbol, string) in &interner {
+            assert_eq!(deserialized.resolve(symbol), Some(string));
+            assert_eq!(deserialized.get(string), Some(symbol));
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_symbols() {
+        let mut interner = StringInterner::new();
+        let symbols = interner.intern_all((0..1000).map(|n| (n * 7 % 1000).to_string()));
+        let json = serde_json::to_string(&interner).unwrap();
+
+        let deserialized: StringInterner = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.eq_ordered(&interner));
+        let bucket: string_hash_interner::StringInterner<
+            DefaultSymbol,
+            FixedHashBuilder,
+            BucketBackend<str, DefaultSymbol>,
+        > = serde_json::from_str(&json).unwrap();
+        for symbol in symbols {
+            assert_eq!(deserialized.resolve(symbol), interner.resolve(symbol));
+            assert_eq!(bucket.resolve(symbol), interner.resolve(symbol));
+        }
+    }
+
+    #[test]
+    fn duplicates_get_first_symbol() {
+        let interner: StringInterner = serde_json::from_str(r#"["aa","bb","aa","cc"]"#).unwrap();
+        assert_eq!(interner.len(), 3);
+        assert!(interner
+            .iter()
+            .map(|(symbol, string)| (symbol.to_usize(), string))
+            .eq([(0, "aa"), (1, "bb"), (2, "cc"),]));
+    }
+
+    #[test]
+    fn symbol_round_trip_works() {
+        let symbol = expect_valid_symbol::<DefaultSymbol>(0);
+        let json = serde_json::to_string(&symbol).unwrap();
+        assert_eq!(json, "0");
+        assert_eq!(
+            serde_json::from_str::<DefaultSymbol>(&json).unwrap(),
+            symbol
+        );
+
+        let symbol = expect_valid_symbol::<SymbolU16>(u16::MAX as usize - 1);
+        let json = serde_json::to_string(&symbol).unwrap();
+        assert_eq!(json, (u16::MAX - 1).to_string());
+        assert_eq!(serde_json::from_str::<SymbolU16>(&json).unwrap(), symbol);
+        assert!(serde_json::from_str::<SymbolU16>(&u16::MAX.to_string()).is_err());
+
+        let symbol = expect_valid_symbol::<SymbolU64>(u32::MAX as usize - 1);
+        let json = serde_json::to_string(&symbol).unwrap();
+        assert_eq!(json, (u32::MAX - 1).to_string());
+        assert_eq!(serde_json::from_str::<SymbolU64>(&json).unwrap(), symbol);
+        assert!(serde_json::from_str::<SymbolU64>(&u64::MAX.to_string()).is_err());
+
+        // Indices above `usize::MAX` would alias smaller ones once truncated.
+        let symbol = expect_valid_symbol::<SymbolU128>(5);
+        assert_eq!(serde_json::from_str::<SymbolU128>("5").unwrap(), symbol);
+        let too_large = (u128::from(u64::MAX) + 6).to_string();
+        #[cfg(target_pointer_width = "64")]
+        assert!(serde_json::from_str::<SymbolU128>(&too_large).is_err());
+    }
+
+    #[test]
+    fn defined_symbol_round_trip_works() {
+        string_hash_interner::define_symbol!(struct FileId(u16) with serde);
+
+        let symbol = expect_valid_symbol::<FileId>(7);
+        let json = serde_json::to_string(&symbol).unwrap();
+        assert_eq!(json, "7");
+        assert_eq!(serde_json::from_str::<FileId>(&json).unwrap(), symbol);
+        assert!(serde_json::from_str::<FileId>(&u16::MAX.to_string()).is_err());
+
+        string_hash_interner::define_symbol!(struct WideId(u128) with serde);
+
+        let symbol = expect_valid_symbol::<WideId>(5);
+        assert_eq!(serde_json::from_str::<WideId>("5").unwrap(), symbol);
+        let too_large = (u128::from(u64::MAX) + 6).to_string();
+        #[cfg(target_pointer_width = "64")]
+        assert!(serde_json::from_str::<WideId>(&too_large).is_err());
+    }
+
+    #[test]
+    fn deserialize_symbol_overflow_fails() {
+        let strings: Vec<String> = (0..256).map(|n| n.to_string()).collect();
+        let json = serde_json::to_string(&strings).unwrap();
+        let result = serde_json::from_str::<string_hash_interner::StringInterner<SymbolU8>>(&json);
+        assert!(result.is_err());
+
+        let json = serde_json::to_string(&strings[..255]).unwrap();
+        let result = serde_json::from_str::<string_hash_interner::StringInterner<SymbolU8>>(&json);
+        assert_eq!(result.unwrap().len(), 255);
+    }
+
+    #[test]
+    fn str_round_trip_works() {
+        round_trip::<str>(&["aa", "bb", "", "aa", "cc"]);
+    }
+
+    #[test]
+    fn bytes_round_trip_works() {
+        round_trip::<[u8]>(&[b"aa", b"\xff\x00", b"", b"aa"]);
+    }
+
+    #[test]
+    fn cstr_round_trip_works() {
+        round_trip::<CStr>(&[c"aa", c"bb", c"", c"aa"]);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_compact {
+    use super::*;
+
+    fn round_trip<H: BuildHasher + Default>(
+        interner: &string_hash_interner::StringInterner<DefaultSymbol, H>,
+    ) {
+        let mut serializer = serde_json::Serializer::new(Vec::new());
+        string_hash_interner::serde_compact::serialize(interner, &mut serializer).unwrap();
+        let json = serializer.into_inner();
+
+        let mut deserializer = serde_json::Deserializer::from_slice(&json);
+        let deserialized: string_hash_interner::StringInterner<DefaultSymbol, H> =
+            string_hash_interner::serde_compact::deserialize(&mut deserializer).unwrap();
+        assert_eq!(deserialized.len(), interner.len());
+        for (symbol, string) in interner {
+            assert_eq!(deserialized.resolve(symbol), Some(string));
+            assert_eq!(deserialized.get(string), Some(symbol));
+        }
+    }
+
+    #[test]
+    fn round_trip_works() {
+        let strings = ["aa", "bb", "", "cc", "aa"];
+        round_trip(&strings.iter().collect::<StringInterner>());
+        round_trip(
+            &strings
+                .iter()
+                .collect::<string_hash_interner::StringInterner<DefaultSymbol, FixedHashBuilder>>(),
+        );
+        round_trip(&StringInterner::new());
+    }
+
+    #[test]
+    fn invalid_data_is_rejected() {
+        fn deserialize(
+            buffer: &[u8],
+            ends: &[(u64, u64)],
+        ) -> Result<StringInterner, serde_json::Error> {
+            let ends = ends
+                .iter()
+                .flat_map(|(end, hash)| [end.to_le_bytes(), hash.to_le_bytes()])
+                .flatten()
+                .collect::<Vec<u8>>();
+            let json = serde_json::to_string(&(buffer, ends)).unwrap();
+            string_hash_interner::serde_compact::deserialize(
+                &mut serde_json::Deserializer::from_str(&json),
+            )
+        }
+        assert!(deserialize(b"ab", &[(1, 0), (2, 0)]).is_ok());
+        // Not UTF-8.
+        assert!(deserialize(b"\xffb", &[(1, 0), (2, 0)]).is_err());
+        // End out of bounds.
+        assert!(deserialize(b"ab", &[(1, 0), (3, 0)]).is_err());
+        // Decreasing ends.
+        assert!(deserialize(b"ab", &[(2, 0), (1, 0)]).is_err());
+        // Trailing bytes.
+        assert!(deserialize(b"ab", &[(1, 0)]).is_err());
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_borrowed {
+    use super::*;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Names {
+        #[serde(with = "string_hash_interner::serde_borrowed")]
+        names: StringInterner,
+    }
+
+    #[test]
+    fn json_round_trip_works() {
+        let mut names = StringInterner::new();
+        let symbols = names.intern_all(["aa", "b\"b", "", "aa", "ü\n"]);
+        let json = serde_json::to_string(&Names { names }).unwrap();
+        let default: StringInterner = serde_json::from_str(&json[9..json.len() - 1]).unwrap();
+
+        let deserialized: Names = serde_json::from_str(&json).unwrap();
+        assert!(deserialized.names.eq_ordered(&default));
+        for (symbol, string) in symbols.into_iter().zip(["aa", "b\"b", "", "aa", "ü\n"]) {
+            assert_eq!(deserialized.names.resolve(symbol), Some(string));
+        }
+
+        let from_reader: Names = serde_json::from_reader(json.as_bytes()).unwrap();
+        assert!(from_reader.names.eq_ordered(&default));
+    }
+
+    #[test]
+    fn cbor_round_trip_works() {
+        let names = StringInterner::from_iter((0..100).map(|n| n.to_string()));
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&Names { names }, &mut cbor).unwrap();
+        let deserialized: Names = ciborium::from_reader(&cbor[..]).unwrap();
+        assert!(deserialized
+            .names
+            .strings()
+            .eq((0..100).map(|n| n.to_string())));
+    }
+
+    #[test]
+    fn invalid_elements_are_rejected() {
+        assert!(serde_json::from_str::<Names>(r#"{"names":["a",1]}"#).is_err());
+
+        #[derive(serde::Deserialize)]
+        struct Small {
+            #[serde(with = "string_hash_interner::serde_borrowed")]
+            #[allow(dead_code)]
+            names: string_hash_interner::StringInterner<SymbolU8>,
+        }
+        let json = serde_json::to_string(&(0..256).map(|n| n.to_string()).collect::<Vec<_>>());
+        let json = format!(r#"{{"names":{}}}"#, json.unwrap());
+        assert!(serde_json::from_str::<Small>(&json).is_err());
+    }
+}
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impl {
+    use super::*;
+    use rkyv::rancor::Error;
+    use string_hash_interner::ArchivedInterner;
+
+    #[test]
+    fn mmap_round_trip_works() {
+        let interner = (0..1000)
+            .map(|n| n.to_string())
+            .chain(["".to_owned(), "ü".to_owned()])
+            .collect::<string_hash_interner::StringInterner<DefaultSymbol, FixedHashBuilder>>();
+        let bytes = rkyv::to_bytes::<Error>(&interner).unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("string-hash-interner-rkyv-{}", std::process::id()));
+        std::fs::write(&path, &bytes).unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let mmap = unsafe { memmap2::Mmap::map(&file) }.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let archived = rkyv::access::<ArchivedInterner<str, DefaultSymbol>, Error>(&mmap).unwrap();
+        assert_eq!(archived.len(), interner.len());
+        assert!(Iterator::eq(archived.iter(), interner.iter()));
+        for (symbol, string) in &interner {
+            assert_eq!(archived.resolve(symbol), Some(string));
+            assert_eq!(unsafe { archived.resolve_unchecked(symbol) }, string);
+            assert_eq!(archived.get_hash(symbol), interner.get_hash(symbol));
+        }
+        assert_eq!(archived.resolve(expect_valid_symbol(5000)), None);
+
+        let fixed: string_hash_interner::StringInterner<DefaultSymbol, FixedHashBuilder> =
+            rkyv::deserialize::<_, Error>(archived).unwrap();
+        let random: StringInterner = rkyv::deserialize::<_, Error>(archived).unwrap();
+        for (symbol, string) in &interner {
+            assert_eq!(fixed.get(string), Some(symbol));
+            assert_eq!(random.get(string), Some(symbol));
+        }
+    }
+
+    #[test]
+    fn invalid_archive_is_rejected() {
+        let interner = StringInterner::from_iter(["aa", "bb"]);
+        let mut bytes = rkyv::to_bytes::<Error>(&interner).unwrap();
+        assert!(rkyv::access::<ArchivedInterner<str, DefaultSymbol>, Error>(&bytes).is_ok());
+        // Too many strings for the symbol type.
+        assert!(rkyv::access::<ArchivedInterner<str, SymbolU8>, Error>(&bytes).is_ok());
+        let big = (0..300).map(|n| n.to_string()).collect::<StringInterner>();
+        let big = rkyv::to_bytes::<Error>(&big).unwrap();
+        assert!(rkyv::access::<ArchivedInterner<str, SymbolU8>, Error>(&big).is_err());
+        // Not UTF-8, the buffer is written first.
+        bytes[0] = 0xff;
+        assert!(rkyv::access::<ArchivedInterner<str, DefaultSymbol>, Error>(&bytes).is_err());
+        assert!(rkyv::access::<ArchivedInterner<[u8], DefaultSymbol>, Error>(&bytes).is_ok());
+    }
+}
+
+#[cfg(feature = "rayon")]
+mod rayon_impl {
+    use super::*;
+    use rayon::prelude::*;
+
+    #[test]
+    fn from_par_iter_works() {
+        let strings: Vec<String> = (0..10_000).map(|n| (n % 3000).to_string()).collect();
+        let expected = StringInterner::from_iter(&strings);
+        let interner: StringInterner = strings.par_iter().collect();
+        assert_eq!(interner.len(), 3000);
+        assert!(interner.eq_ordered(&expected));
+        for string in &strings {
+            assert_eq!(
+                interner.resolve(interner.get(string).unwrap()),
+                Some(&**string)
+            );
+        }
+    }
+
+    #[test]
+    fn par_extend_works() {
+        let mut interner = StringInterner::new();
+        let aa = interner.intern("aa");
+        interner.par_extend(vec!["bb", "aa", "cc", "bb"]);
+        assert!(interner.iter().map(|(_, s)| s).eq(["aa", "bb", "cc"]));
+        assert_eq!(interner.get("aa"), Some(aa));
+
+        let mut bucket = string_hash_interner::StringInterner::<
+            DefaultSymbol,
+            DefaultHashBuilder,
+            BucketBackend<str, DefaultSymbol>,
+        >::new();
+        bucket.par_extend((0..100).into_par_iter().map(|n| n.to_string()));
+        assert!(bucket.strings().eq((0..100).map(|n| n.to_string())));
     }
 }